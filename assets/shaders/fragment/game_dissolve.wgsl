@@ -0,0 +1,45 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct MaterialTime { time: f32, }
+struct MaterialColor { color: vec4<f32>, }
+// x = threshold (0.0 whole - 1.0 gone), y = edge width, z = noise cells across the sprite
+struct MaterialParams { params: vec4<f32>, }
+
+@group(2) @binding(0) var<uniform> time_u: MaterialTime;
+@group(2) @binding(1) var<uniform> color_u: MaterialColor;
+@group(2) @binding(2) var<uniform> params_u: MaterialParams;
+@group(2) @binding(3) var color_texture: texture_2d<f32>;
+@group(2) @binding(4) var color_sampler: sampler;
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2(127.1, 311.7))) * 43758.5453);
+}
+
+fn value_noise(p: vec2<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    return mix(
+        mix(hash(i), hash(i + vec2(1.0, 0.0)), u.x),
+        mix(hash(i + vec2(0.0, 1.0)), hash(i + vec2(1.0, 1.0)), u.x),
+        u.y
+    );
+}
+
+// -----------------------------------------------------------------------------
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texture = textureSample(color_texture, color_sampler, in.uv);
+    let params = params_u.params;
+
+    // texels below the threshold are gone, the ones just above it glow
+    let n = 0.65 * value_noise(in.uv * params.z) + 0.35 * value_noise(in.uv * params.z * 3.0);
+    let threshold = params.x * (1.0 + params.y);
+    if n < threshold - params.y {
+        return vec4(0.0);
+    }
+
+    let flicker = 0.85 + 0.15 * sin(time_u.time * 12.0 + n * 20.0);
+    let edge = (1.0 - smoothstep(threshold - params.y, threshold, n)) * color_u.color.a * flicker;
+    return vec4(mix(texture.rgb, color_u.color.rgb, edge), texture.a);
+}
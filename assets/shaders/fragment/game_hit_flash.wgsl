@@ -0,0 +1,27 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct MaterialTime { time: f32, }
+struct MaterialColor { color: vec4<f32>, }
+// x = flash amount (0.0 - 1.0), y = flickers per second (0 for a solid flash)
+struct MaterialParams { params: vec4<f32>, }
+
+@group(2) @binding(0) var<uniform> time_u: MaterialTime;
+@group(2) @binding(1) var<uniform> color_u: MaterialColor;
+@group(2) @binding(2) var<uniform> params_u: MaterialParams;
+@group(2) @binding(3) var color_texture: texture_2d<f32>;
+@group(2) @binding(4) var color_sampler: sampler;
+
+// -----------------------------------------------------------------------------
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texture = textureSample(color_texture, color_sampler, in.uv);
+
+    var flash = params_u.params.x;
+    if params_u.params.y > 0.0 {
+        flash *= step(0.5, fract(time_u.time * params_u.params.y));
+    }
+
+    // the alpha of the sprite is kept, only its color is replaced
+    let rgb = mix(texture.rgb, color_u.color.rgb, clamp(flash, 0.0, 1.0) * color_u.color.a);
+    return vec4(rgb, texture.a);
+}
@@ -0,0 +1,37 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct MaterialTime { time: f32, }
+struct MaterialColor { color: vec4<f32>, }
+// x = thickness in texels, y = pulses per second (0 for a steady outline)
+struct MaterialParams { params: vec4<f32>, }
+
+@group(2) @binding(0) var<uniform> time_u: MaterialTime;
+@group(2) @binding(1) var<uniform> color_u: MaterialColor;
+@group(2) @binding(2) var<uniform> params_u: MaterialParams;
+@group(2) @binding(3) var color_texture: texture_2d<f32>;
+@group(2) @binding(4) var color_sampler: sampler;
+
+const PI: f32 = 3.141592653589793;
+
+// -----------------------------------------------------------------------------
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texture = textureSample(color_texture, color_sampler, in.uv);
+    let texel = params_u.params.x / vec2<f32>(textureDimensions(color_texture));
+
+    // the outline is drawn on transparent texels next to opaque ones, so it never covers the sprite
+    var neighbours = 0.0;
+    neighbours = max(neighbours, textureSample(color_texture, color_sampler, in.uv + vec2(texel.x, 0.0)).a);
+    neighbours = max(neighbours, textureSample(color_texture, color_sampler, in.uv - vec2(texel.x, 0.0)).a);
+    neighbours = max(neighbours, textureSample(color_texture, color_sampler, in.uv + vec2(0.0, texel.y)).a);
+    neighbours = max(neighbours, textureSample(color_texture, color_sampler, in.uv - vec2(0.0, texel.y)).a);
+
+    var pulse = 1.0;
+    if params_u.params.y > 0.0 {
+        pulse = 0.5 + 0.5 * cos(time_u.time * params_u.params.y * 2.0 * PI);
+    }
+
+    let outline = color_u.color.a * pulse * neighbours * (1.0 - texture.a);
+    let rgb = mix(texture.rgb, color_u.color.rgb, outline / max(outline + texture.a, 0.0001));
+    return vec4(rgb, texture.a + outline);
+}
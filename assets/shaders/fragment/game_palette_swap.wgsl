@@ -0,0 +1,42 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct MaterialTime { time: f32, }
+// x = palette row, y = first cycled index, z = cycled index count, w = cycle steps per second
+struct MaterialParams { params: vec4<f32>, }
+
+@group(2) @binding(0) var<uniform> time_u: MaterialTime;
+@group(2) @binding(1) var<uniform> params_u: MaterialParams;
+@group(2) @binding(2) var color_texture: texture_2d<f32>;
+@group(2) @binding(3) var color_sampler: sampler;
+// one palette per row, one color per texel
+@group(2) @binding(4) var palette_texture: texture_2d<f32>;
+
+// -----------------------------------------------------------------------------
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    // the red channel of the sprite holds the palette index
+    let texture = textureSample(color_texture, color_sampler, in.uv);
+    if texture.a <= 0.0 {
+        return vec4(0.0);
+    }
+
+    let palette_size = textureDimensions(palette_texture);
+    let params = params_u.params;
+    var index = i32(round(texture.r * 255.0));
+
+    // indices inside the cycle rotate over time, for water, fire and the like
+    let cycle_start = i32(params.y);
+    let cycle_length = i32(params.z);
+    if cycle_length > 0 && index >= cycle_start && index < cycle_start + cycle_length {
+        let step = i32(floor(time_u.time * params.w));
+        index = cycle_start + (index - cycle_start + step) % cycle_length;
+    }
+
+    let texel = clamp(
+        vec2(index, i32(params.x)),
+        vec2(0),
+        vec2<i32>(palette_size) - 1
+    );
+    let color = textureLoad(palette_texture, texel, 0);
+    return vec4(color.rgb, color.a * texture.a);
+}
@@ -0,0 +1,30 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct MaterialTime { time: f32, }
+struct MaterialColor { color: vec4<f32>, }
+// x = occluded (0.0 hidden - 1.0 shown), y = pulses per second (0 for a steady silhouette)
+struct MaterialParams { params: vec4<f32>, }
+
+@group(2) @binding(0) var<uniform> time_u: MaterialTime;
+@group(2) @binding(1) var<uniform> color_u: MaterialColor;
+@group(2) @binding(2) var<uniform> params_u: MaterialParams;
+@group(2) @binding(3) var color_texture: texture_2d<f32>;
+@group(2) @binding(4) var color_sampler: sampler;
+
+const PI: f32 = 3.141592653589793;
+
+// -----------------------------------------------------------------------------
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texture = textureSample(color_texture, color_sampler, in.uv);
+
+    var pulse = 1.0;
+    if params_u.params.y > 0.0 {
+        pulse = 0.75 + 0.25 * cos(time_u.time * params_u.params.y * 2.0 * PI);
+    }
+
+    // the shape of the sprite in a flat color, diagonal hatching keeps it readable over busy art
+    let hatch = 0.8 + 0.2 * step(0.5, fract((in.world_position.x + in.world_position.y) * 0.25));
+    let alpha = texture.a * color_u.color.a * clamp(params_u.params.x, 0.0, 1.0) * pulse * hatch;
+    return vec4(color_u.color.rgb, alpha);
+}
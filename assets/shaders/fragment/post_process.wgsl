@@ -0,0 +1,107 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::globals::Globals
+
+// same layout as PostProcessUniform, colors are linear
+struct PostProcessUniform {
+    vignette_color: vec4<f32>,
+    vignette_intensity: f32,
+    vignette_radius: f32,
+    vignette_softness: f32,
+    aberration_intensity: f32,
+    crt_curvature: f32,
+    scanline_intensity: f32,
+    scanline_count: f32,
+    grain_intensity: f32,
+    grain_size: f32,
+    exposure: f32,
+    contrast: f32,
+    saturation: f32,
+    tint: vec4<f32>,
+}
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var screen_sampler: sampler;
+@group(0) @binding(2) var<uniform> settings: PostProcessUniform;
+@group(0) @binding(3) var<uniform> globals: Globals;
+
+const PI: f32 = 3.141592653589793;
+const MIDDLE_GREY: f32 = 0.18;
+
+fn screen(uv: vec2<f32>) -> vec4<f32> {
+    return textureSample(screen_texture, screen_sampler, uv);
+}
+
+fn screen_size() -> vec2<f32> {
+    return vec2<f32>(textureDimensions(screen_texture));
+}
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3(0.2126, 0.7152, 0.0722));
+}
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2(127.1, 311.7))) * 43758.5453);
+}
+
+// every entry point reads the output of the effect before it in the chain
+// -----------------------------------------------------------------------------
+@fragment
+fn vignette(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = screen(in.uv);
+
+    // round on any aspect ratio, radius 1.0 reaches the middle of the longer edge
+    let size = screen_size();
+    let centred = (in.uv - 0.5) * 2.0 * vec2(size.x / max(size.x, size.y), size.y / max(size.x, size.y));
+    let shade = smoothstep(settings.vignette_radius, settings.vignette_radius - settings.vignette_softness, length(centred));
+
+    let amount = settings.vignette_intensity * (1.0 - shade) * settings.vignette_color.a;
+    return vec4(mix(color.rgb, settings.vignette_color.rgb, amount), color.a);
+}
+
+@fragment
+fn chromatic_aberration(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // grows towards the edges, intensity is the offset in pixels at the corners
+    let offset = (in.uv - 0.5) * 2.0 * settings.aberration_intensity / screen_size();
+
+    let green = screen(in.uv);
+    let red = screen(in.uv + offset).r;
+    let blue = screen(in.uv - offset).b;
+    return vec4(red, green.g, blue, green.a);
+}
+
+@fragment
+fn crt(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // bulge the screen out from the centre, whatever falls off the edge is black
+    var centred = in.uv * 2.0 - 1.0;
+    centred += centred * centred.yx * centred.yx * settings.crt_curvature;
+    let uv = centred * 0.5 + 0.5;
+    if any(uv < vec2(0.0)) || any(uv > vec2(1.0)) {
+        return vec4(0.0, 0.0, 0.0, 1.0);
+    }
+
+    let color = screen(uv);
+    let scanline = sin(uv.y * settings.scanline_count * PI) * 0.5 + 0.5;
+    return vec4(color.rgb * (1.0 - settings.scanline_intensity * scanline), color.a);
+}
+
+@fragment
+fn film_grain(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = screen(in.uv);
+
+    // new grain every frame, clumped into grain_size pixels, strongest in the mid tones
+    let cell = floor(in.uv * screen_size() / max(settings.grain_size, 1.0));
+    let grain = hash(cell + fract(globals.time) * 113.0) - 0.5;
+    let response = 1.0 - abs(luminance(color.rgb) * 2.0 - 1.0);
+    return vec4(max(color.rgb + grain * settings.grain_intensity * response, vec3(0.0)), color.a);
+}
+
+@fragment
+fn color_grading(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = screen(in.uv);
+
+    var graded = max(color.rgb * exp2(settings.exposure), vec3(0.0));
+    graded = MIDDLE_GREY * pow(graded / MIDDLE_GREY, vec3(settings.contrast));
+    graded = mix(vec3(luminance(graded)), graded, settings.saturation);
+    graded = max(graded, vec3(0.0)) * settings.tint.rgb;
+    return vec4(graded, color.a);
+}
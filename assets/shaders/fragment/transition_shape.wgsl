@@ -0,0 +1,152 @@
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+#import "shaders/utils/sd_shapes.wgsl"::{sdCircle, sdRhombus, sdHexagon}
+#import "shaders/utils/easings.wgsl"::{
+    lin,
+    quadraticIn, quadraticOut, quadraticInOut,
+    cubicIn, cubicOut, cubicInOut,
+    quarticIn, quarticOut, quarticInOut,
+    qinticIn, qinticOut, qinticInOut,
+    sineIn, sineOut, sineInOut,
+    exponentialIn, exponentialOut, exponentialInOut,
+    circularIn, circularOut, circularInOut,
+    backIn, backOut, backInOut,
+    elasticIn, elasticOut, elasticInOut,
+    bounceIn, bounceOut, bounceInOut,
+}
+
+struct MaterialTime { time: f32, }
+struct MaterialColor { color: vec4<f32>, }
+// x = shape (0 wipe, 1 iris, 2 tiles, 3 noise), y = easing, z = 1 when revealing, w = tile shape (0 diamond, 1 hexagon)
+struct MaterialSettings { settings: vec4<u32>, }
+// wipe: xy = direction | iris: xy = centre in uv | tiles: z = tile size in pixels
+struct MaterialParams { params: vec4<f32>, }
+
+@group(1) @binding(0) var<uniform> time_u: MaterialTime;
+@group(1) @binding(1) var<uniform> color_u: MaterialColor;
+@group(1) @binding(2) var<uniform> settings_u: MaterialSettings;
+@group(1) @binding(3) var<uniform> params_u: MaterialParams;
+
+// width of the soft edge, in uv for wipe and noise, in pixels for iris and tiles
+const EDGE_UV: f32 = 0.02;
+const EDGE_PX: f32 = 1.5;
+
+// same order as TransitionEasing
+fn ease(easing: u32, t: f32) -> f32 {
+    switch easing {
+        case 1u: { return quadraticIn(t); }
+        case 2u: { return quadraticOut(t); }
+        case 3u: { return quadraticInOut(t); }
+        case 4u: { return cubicIn(t); }
+        case 5u: { return cubicOut(t); }
+        case 6u: { return cubicInOut(t); }
+        case 7u: { return quarticIn(t); }
+        case 8u: { return quarticOut(t); }
+        case 9u: { return quarticInOut(t); }
+        case 10u: { return qinticIn(t); }
+        case 11u: { return qinticOut(t); }
+        case 12u: { return qinticInOut(t); }
+        case 13u: { return sineIn(t); }
+        case 14u: { return sineOut(t); }
+        case 15u: { return sineInOut(t); }
+        case 16u: { return exponentialIn(t); }
+        case 17u: { return exponentialOut(t); }
+        case 18u: { return exponentialInOut(t); }
+        case 19u: { return circularIn(t); }
+        case 20u: { return circularOut(t); }
+        case 21u: { return circularInOut(t); }
+        case 22u: { return backIn(t); }
+        case 23u: { return backOut(t); }
+        case 24u: { return backInOut(t); }
+        case 25u: { return elasticIn(t); }
+        case 26u: { return elasticOut(t); }
+        case 27u: { return elasticInOut(t); }
+        case 28u: { return bounceIn(t); }
+        case 29u: { return bounceOut(t); }
+        case 30u: { return bounceInOut(t); }
+        default: { return lin(t); }
+    }
+}
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2(127.1, 311.7))) * 43758.5453);
+}
+
+fn value_noise(p: vec2<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    return mix(
+        mix(hash(i), hash(i + vec2(1.0, 0.0)), u.x),
+        mix(hash(i + vec2(0.0, 1.0)), hash(i + vec2(1.0, 1.0)), u.x),
+        u.y
+    );
+}
+
+// the screen is fully covered at 1.0 and fully visible at 0.0
+fn wipe(uv: vec2<f32>, direction: vec2<f32>, coverage: f32) -> f32 {
+    let along = dot(uv - 0.5, direction) + 0.5;
+    let front = coverage * (1.0 + EDGE_UV);
+    return 1.0 - smoothstep(front - EDGE_UV, front, along);
+}
+
+fn iris(uv: vec2<f32>, size: vec2<f32>, centre: vec2<f32>, coverage: f32) -> f32 {
+    let p = (uv - centre) * size;
+
+    // the circle starts large enough to clear the corner furthest from the centre
+    let corners = max(abs(centre), abs(vec2(1.0) - centre)) * size;
+    let radius = mix(length(corners) + EDGE_PX, -EDGE_PX, coverage);
+
+    return smoothstep(-EDGE_PX, EDGE_PX, sdCircle(p, radius));
+}
+
+fn tiles(uv: vec2<f32>, size: vec2<f32>, tile_size: f32, tile_shape: u32, coverage: f32) -> f32 {
+    let cell = uv * size / tile_size;
+    let p = fract(cell) - 0.5;
+
+    // tiles grow in a diagonal sweep from the top left
+    let sweep = (floor(cell.x) + floor(cell.y)) / (ceil(size.x / tile_size) + ceil(size.y / tile_size));
+    let grow = clamp(coverage * 2.0 - sweep, 0.0, 1.0);
+    if grow <= 0.0 {
+        return 0.0;
+    }
+
+    // fully grown tiles reach past the corners of their cell, so neighbours overlap
+    var d: f32;
+    if tile_shape == 1u {
+        d = sdHexagon(p, grow * 0.85);
+    } else {
+        d = sdRhombus(p, vec2(grow * 1.25));
+    }
+
+    let edge = EDGE_PX / tile_size;
+    return 1.0 - smoothstep(-edge, edge, d);
+}
+
+fn noise(uv: vec2<f32>, size: vec2<f32>, coverage: f32) -> f32 {
+    let aspect = vec2(size.x / max(size.y, 1.0), 1.0);
+    let n = 0.65 * value_noise(uv * aspect * 8.0) + 0.35 * value_noise(uv * aspect * 24.0);
+    let front = coverage * (1.0 + EDGE_UV);
+    return 1.0 - smoothstep(front - EDGE_UV, front, n);
+}
+
+// -----------------------------------------------------------------------------
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let settings = settings_u.settings;
+    let params = params_u.params;
+
+    var coverage = ease(settings.y, clamp(time_u.time, 0.0, 1.0));
+    if settings.z == 1u {
+        coverage = 1.0 - coverage;
+    }
+
+    var alpha: f32;
+    switch settings.x {
+        case 0u: { alpha = wipe(in.uv, params.xy, coverage); }
+        case 1u: { alpha = iris(in.uv, in.size, params.xy, coverage); }
+        case 2u: { alpha = tiles(in.uv, in.size, params.z, settings.w, coverage); }
+        default: { alpha = noise(in.uv, in.size, coverage); }
+    }
+
+    return vec4(color_u.color.rgb, color_u.color.a * clamp(alpha, 0.0, 1.0));
+}
@@ -0,0 +1,201 @@
+use crate::config::settings::{SaveSettings, Settings};
+
+use bevy::{
+    audio::{AudioSink, AudioSinkPlayback, SpatialAudioSink},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+pub const VOLUME_STEP: f32 = 0.1;
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct AudioBusesPlugin;
+
+impl Plugin for AudioBusesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EditBusVolume>()
+            .add_event::<EditBusMute>()
+            .add_systems(
+                Update,
+                (
+                    handle_edit_bus_volume,
+                    handle_edit_bus_mute,
+                    apply_bus_volumes,
+                ),
+            );
+    }
+}
+
+// components ------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// add to an entity playing audio to route it, sinks without a bus only follow Master
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AudioBus {
+    Master,
+    Music,
+    Sfx,
+    Ui,
+}
+
+impl AudioBus {
+    pub const ALL: [AudioBus; 4] = [
+        AudioBus::Master,
+        AudioBus::Music,
+        AudioBus::Sfx,
+        AudioBus::Ui,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AudioBus::Master => "Master",
+            AudioBus::Music => "Music",
+            AudioBus::Sfx => "SFX",
+            AudioBus::Ui => "UI",
+        }
+    }
+}
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BusSettings {
+    pub volume: f32, // 0.0 - 1.0
+    pub muted: bool,
+}
+
+impl Default for BusSettings {
+    fn default() -> BusSettings {
+        BusSettings {
+            volume: 1.0,
+            muted: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: BusSettings,
+    pub music: BusSettings,
+    pub sfx: BusSettings,
+    pub ui: BusSettings,
+}
+
+impl AudioSettings {
+    pub fn bus(&self, bus: AudioBus) -> &BusSettings {
+        match bus {
+            AudioBus::Master => &self.master,
+            AudioBus::Music => &self.music,
+            AudioBus::Sfx => &self.sfx,
+            AudioBus::Ui => &self.ui,
+        }
+    }
+
+    pub fn bus_mut(&mut self, bus: AudioBus) -> &mut BusSettings {
+        match bus {
+            AudioBus::Master => &mut self.master,
+            AudioBus::Music => &mut self.music,
+            AudioBus::Sfx => &mut self.sfx,
+            AudioBus::Ui => &mut self.ui,
+        }
+    }
+
+    // the volume a sink on this bus plays at, every bus is scaled by Master
+    pub fn effective_volume(&self, bus: Option<AudioBus>) -> f32 {
+        let bus_volume = |bus_settings: &BusSettings| {
+            if bus_settings.muted {
+                0.0
+            } else {
+                bus_settings.volume
+            }
+        };
+
+        let master_volume = bus_volume(&self.master);
+        match bus {
+            None | Some(AudioBus::Master) => master_volume,
+            Some(bus) => master_volume * bus_volume(self.bus(bus)),
+        }
+    }
+}
+
+// events ----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Event)]
+pub struct EditBusVolume {
+    pub bus: AudioBus,
+    pub volume: f32,
+}
+
+#[derive(Event)]
+pub struct EditBusMute {
+    pub bus: AudioBus,
+}
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn handle_edit_bus_volume(
+    mut settings: ResMut<Settings>,
+    mut read_edit_bus_volume: EventReader<EditBusVolume>,
+    mut write_save_settings: EventWriter<SaveSettings>,
+) {
+    for volume_edit in read_edit_bus_volume.read() {
+        let bus_settings = settings.audio.bus_mut(volume_edit.bus);
+        bus_settings.volume = volume_edit.volume.clamp(0.0, 1.0);
+        write_save_settings.send(SaveSettings);
+        info!(
+            "{:?} volume changed to: {:?}",
+            volume_edit.bus, bus_settings.volume
+        );
+    }
+}
+
+pub fn handle_edit_bus_mute(
+    mut settings: ResMut<Settings>,
+    mut read_edit_bus_mute: EventReader<EditBusMute>,
+    mut write_save_settings: EventWriter<SaveSettings>,
+) {
+    for mute_edit in read_edit_bus_mute.read() {
+        let bus_settings = settings.audio.bus_mut(mute_edit.bus);
+        bus_settings.muted = !bus_settings.muted;
+        write_save_settings.send(SaveSettings);
+        info!(
+            "{:?} mute changed to: {:?}",
+            mute_edit.bus, bus_settings.muted
+        );
+    }
+}
+
+// the sound's own PlaybackSettings volume is kept as a per sound gain on top of its bus
+//  new sinks pick up the bus volume as soon as bevy creates them
+pub fn apply_bus_volumes(
+    settings: Res<Settings>,
+    sink_query: Query<(Ref<AudioSink>, Option<&AudioBus>, Option<&PlaybackSettings>)>,
+    spatial_sink_query: Query<(
+        Ref<SpatialAudioSink>,
+        Option<&AudioBus>,
+        Option<&PlaybackSettings>,
+    )>,
+) {
+    for (sink, bus, playback) in sink_query.iter() {
+        if settings.is_changed() || sink.is_added() {
+            sink.set_volume(sink_volume(&settings, bus, playback));
+        }
+    }
+    for (sink, bus, playback) in spatial_sink_query.iter() {
+        if settings.is_changed() || sink.is_added() {
+            sink.set_volume(sink_volume(&settings, bus, playback));
+        }
+    }
+}
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+fn sink_volume(
+    settings: &Settings,
+    bus: Option<&AudioBus>,
+    playback: Option<&PlaybackSettings>,
+) -> f32 {
+    let sound_volume = playback.map_or(1.0, |playback| playback.volume.get());
+    settings.audio.effective_volume(bus.copied()) * sound_volume
+}
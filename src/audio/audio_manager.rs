@@ -0,0 +1,13 @@
+use super::audio_buses;
+
+use bevy::prelude::*;
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct AudioManagerPlugin;
+
+impl Plugin for AudioManagerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((audio_buses::AudioBusesPlugin,));
+    }
+}
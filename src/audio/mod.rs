@@ -0,0 +1,2 @@
+pub mod audio_buses;
+pub mod audio_manager;
@@ -0,0 +1,69 @@
+use super::settings::{SaveSettings, Settings};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const SCREEN_SHAKE_STEP: f32 = 0.25;
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EditScreenShake>()
+            .add_systems(Update, handle_edit_screen_shake);
+    }
+}
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    pub screen_shake: f32, // 0.0 (off) - 1.0, scales every camera shake
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> AccessibilitySettings {
+        AccessibilitySettings { screen_shake: 1.0 }
+    }
+}
+
+impl AccessibilitySettings {
+    pub fn screen_shake_label(&self) -> String {
+        if self.screen_shake <= 0.0 {
+            "Off".to_string()
+        } else {
+            format!("{:.0}%", self.screen_shake * 100.0)
+        }
+    }
+}
+
+// events ----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// steps the intensity down, wrapping from off back to full
+#[derive(Event)]
+pub struct EditScreenShake;
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn handle_edit_screen_shake(
+    mut settings: ResMut<Settings>,
+    mut read_edit_screen_shake: EventReader<EditScreenShake>,
+    mut write_save_settings: EventWriter<SaveSettings>,
+) {
+    for _ in read_edit_screen_shake.read() {
+        let accessibility = &mut settings.accessibility;
+        accessibility.screen_shake = if accessibility.screen_shake <= 0.0 {
+            1.0
+        } else {
+            (accessibility.screen_shake - SCREEN_SHAKE_STEP).max(0.0)
+        };
+        write_save_settings.send(SaveSettings);
+        info!(
+            "Screen shake changed to: {}",
+            accessibility.screen_shake_label()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::{controls, windows};
+use super::{accessibility, controls, monitors, settings, windows};
 
 use bevy::prelude::*;
 
@@ -8,6 +8,12 @@ pub struct ConfigManagerPlugin;
 
 impl Plugin for ConfigManagerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((controls::MyControlsPlugin, windows::WindowsPlugin));
+        app.add_plugins((
+            accessibility::AccessibilityPlugin,
+            controls::MyControlsPlugin,
+            monitors::MonitorsPlugin,
+            settings::SettingsPlugin,
+            windows::WindowsPlugin,
+        ));
     }
 }
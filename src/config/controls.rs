@@ -1,14 +1,21 @@
+use super::settings::{read_config, write_config};
+
 use bevy::{
     input::{gamepad::GamepadEvent, keyboard::KeyboardInput},
     prelude::*,
 };
 use leafwing_input_manager::{
     action_state::ActionState,
-    axislike::{DualAxis, VirtualDPad},
+    axislike::{DualAxis, VirtualAxis, VirtualDPad},
+    buttonlike::MouseWheelDirection,
     input_map::InputMap,
     prelude::InputManagerPlugin,
+    user_input::{InputKind, UserInput},
     Actionlike,
 };
+use serde::{Deserialize, Serialize};
+
+pub const CONTROLS_FILE_NAME: &str = "controls.ron";
 
 // plugins ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -20,11 +27,16 @@ impl Plugin for MyControlsPlugin {
             .init_resource::<ActionState<InputAction>>()
             .insert_resource(InputAction::default_input_map())
             .init_state::<ActiveInput>()
+            .add_event::<EditBinding>()
+            .add_event::<ResetBindings>()
+            .add_systems(PreStartup, load_input_map)
             .add_systems(
                 Update,
                 (
                     activate_gamepad.run_if(in_state(ActiveInput::MouseKeyboard)),
                     activate_mkb.run_if(in_state(ActiveInput::Gamepad)),
+                    handle_edit_binding,
+                    handle_reset_bindings,
                 ),
             );
     }
@@ -41,33 +53,299 @@ enum ActiveInput {
 
 // resources -------------------------------------------------------------------
 // -----------------------------------------------------------------------------
-#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
+#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect, Serialize, Deserialize)]
 pub enum InputAction {
     Move,
     Look,
     Select,
+    Back,
+    Zoom, // positive zooms in
+    QuickSave,
 }
 
 impl InputAction {
-    fn default_input_map() -> InputMap<Self> {
+    // listed in the order they appear on the controls screen
+    pub const ALL: [InputAction; 6] = [
+        InputAction::Move,
+        InputAction::Look,
+        InputAction::Zoom,
+        InputAction::Select,
+        InputAction::Back,
+        InputAction::QuickSave,
+    ];
+
+    // axis actions are bound to presets instead of a single captured key / button
+    pub fn is_axis(&self) -> bool {
+        matches!(
+            self,
+            InputAction::Move | InputAction::Look | InputAction::Zoom
+        )
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputAction::Move => "Move",
+            InputAction::Look => "Look",
+            InputAction::Select => "Select",
+            InputAction::Back => "Back",
+            InputAction::Zoom => "Zoom",
+            InputAction::QuickSave => "Quick Save",
+        }
+    }
+
+    pub fn default_input_map() -> InputMap<Self> {
         let mut input_map = InputMap::default();
 
         // default gamepad input bindings
         input_map.insert(Self::Move, DualAxis::left_stick());
         input_map.insert(Self::Look, DualAxis::right_stick());
         input_map.insert(Self::Select, GamepadButtonType::RightTrigger);
+        input_map.insert(Self::Back, GamepadButtonType::East);
+        input_map.insert(Self::Zoom, zoom_triggers());
+        input_map.insert(Self::QuickSave, GamepadButtonType::Select);
 
         // default kbm input bindings
         input_map.insert(Self::Move, VirtualDPad::wasd());
         input_map.insert(Self::Look, VirtualDPad::arrow_keys());
         input_map.insert(Self::Select, KeyCode::Space);
+        input_map.insert(Self::Back, KeyCode::Escape);
+        input_map.insert(Self::Zoom, zoom_mouse_wheel());
+        input_map.insert(Self::QuickSave, KeyCode::F5);
 
         input_map
     }
 }
 
+// every action keeps one keyboard and one gamepad binding
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingDevice {
+    Keyboard,
+    Gamepad,
+}
+
+// events ----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// replaces the action's binding for the input's device, conflicts are checked by the sender
+#[derive(Event)]
+pub struct EditBinding {
+    pub action: InputAction,
+    pub input: UserInput,
+}
+
+#[derive(Event)]
+pub struct ResetBindings;
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn zoom_mouse_wheel() -> VirtualAxis {
+    VirtualAxis {
+        negative: MouseWheelDirection::Down.into(),
+        positive: MouseWheelDirection::Up.into(),
+    }
+}
+
+pub fn zoom_triggers() -> VirtualAxis {
+    VirtualAxis {
+        negative: GamepadButtonType::LeftTrigger2.into(),
+        positive: GamepadButtonType::RightTrigger2.into(),
+    }
+}
+
+pub fn axis_presets(action: InputAction, device: BindingDevice) -> Vec<(&'static str, UserInput)> {
+    match action {
+        InputAction::Zoom => zoom_presets(device),
+        _ => direction_presets(device),
+    }
+}
+
+pub fn zoom_presets(device: BindingDevice) -> Vec<(&'static str, UserInput)> {
+    match device {
+        BindingDevice::Keyboard => vec![
+            ("Mouse Wheel", zoom_mouse_wheel().into()),
+            (
+                "Q / E",
+                VirtualAxis::from_keys(KeyCode::KeyQ, KeyCode::KeyE).into(),
+            ),
+            (
+                "- / =",
+                VirtualAxis::from_keys(KeyCode::Minus, KeyCode::Equal).into(),
+            ),
+        ],
+        BindingDevice::Gamepad => vec![
+            ("Triggers", zoom_triggers().into()),
+            ("D-Pad Up / Down", VirtualAxis::vertical_dpad().into()),
+            // the right bumper is Select by default, so the bumpers are not offered as a pair
+            (
+                "West / North",
+                VirtualAxis {
+                    negative: GamepadButtonType::West.into(),
+                    positive: GamepadButtonType::North.into(),
+                }
+                .into(),
+            ),
+        ],
+    }
+}
+
+pub fn direction_presets(device: BindingDevice) -> Vec<(&'static str, UserInput)> {
+    match device {
+        BindingDevice::Keyboard => vec![
+            ("WASD", VirtualDPad::wasd().into()),
+            ("Arrows", VirtualDPad::arrow_keys().into()),
+            (
+                "IJKL",
+                VirtualDPad {
+                    up: KeyCode::KeyI.into(),
+                    down: KeyCode::KeyK.into(),
+                    left: KeyCode::KeyJ.into(),
+                    right: KeyCode::KeyL.into(),
+                }
+                .into(),
+            ),
+        ],
+        BindingDevice::Gamepad => vec![
+            ("Left Stick", DualAxis::left_stick().into()),
+            ("Right Stick", DualAxis::right_stick().into()),
+            ("D-Pad", VirtualDPad::dpad().into()),
+        ],
+    }
+}
+
+// the raw buttons / axes a binding is made of, used to compare bindings for conflicts
+pub fn input_kinds(input: &UserInput) -> Vec<InputKind> {
+    match input {
+        UserInput::Single(kind) => vec![*kind],
+        UserInput::Chord(kinds) => kinds.clone(),
+        UserInput::VirtualDPad(dpad) => vec![dpad.up, dpad.down, dpad.left, dpad.right],
+        UserInput::VirtualAxis(axis) => vec![axis.negative, axis.positive],
+    }
+}
+
+pub fn binding_device(input: &UserInput) -> BindingDevice {
+    let is_gamepad = input_kinds(input).iter().any(|kind| {
+        matches!(
+            kind,
+            InputKind::GamepadButton(_) | InputKind::SingleAxis(_) | InputKind::DualAxis(_)
+        )
+    });
+
+    if is_gamepad {
+        BindingDevice::Gamepad
+    } else {
+        BindingDevice::Keyboard
+    }
+}
+
+pub fn binding(
+    input_map: &InputMap<InputAction>,
+    action: InputAction,
+    device: BindingDevice,
+) -> Option<&UserInput> {
+    input_map
+        .get(&action)?
+        .iter()
+        .find(|input| binding_device(input) == device)
+}
+
+// the other action already using any part of the input, if there is one
+pub fn find_conflict(
+    input_map: &InputMap<InputAction>,
+    action: InputAction,
+    input: &UserInput,
+) -> Option<InputAction> {
+    let kinds = input_kinds(input);
+
+    input_map
+        .iter()
+        .filter(|(other_action, _)| **other_action != action)
+        .find(|(_, other_inputs)| {
+            other_inputs.iter().any(|other_input| {
+                input_kinds(other_input)
+                    .iter()
+                    .any(|other_kind| kinds.contains(other_kind))
+            })
+        })
+        .map(|(other_action, _)| *other_action)
+}
+
+pub fn binding_label(input: &UserInput) -> String {
+    let device = binding_device(input);
+    let preset_label = direction_presets(device)
+        .into_iter()
+        .chain(zoom_presets(device))
+        .find(|(_, preset)| preset == input)
+        .map(|(label, _)| label.to_string());
+    if let Some(label) = preset_label {
+        return label;
+    }
+
+    match input {
+        UserInput::Single(InputKind::PhysicalKey(key_code)) => format!("{:?}", key_code),
+        UserInput::Single(InputKind::GamepadButton(button_type)) => format!("{:?}", button_type),
+        _ => "Custom".to_string(),
+    }
+}
+
 // systems ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
+pub fn load_input_map(mut input_map: ResMut<InputMap<InputAction>>) {
+    let Some(mut loaded_input_map) = read_config::<InputMap<InputAction>>(CONTROLS_FILE_NAME)
+    else {
+        return;
+    };
+
+    // actions added since the file was written fall back to their default bindings
+    let default_input_map = InputAction::default_input_map();
+    for action in InputAction::ALL {
+        if loaded_input_map.get(&action).is_none() {
+            if let Some(default_inputs) = default_input_map.get(&action) {
+                loaded_input_map.insert_one_to_many(action, default_inputs.clone());
+            }
+        }
+    }
+
+    *input_map = loaded_input_map;
+}
+
+pub fn handle_edit_binding(
+    mut input_map: ResMut<InputMap<InputAction>>,
+    mut read_edit_binding: EventReader<EditBinding>,
+) {
+    let mut is_edited = false;
+
+    for binding_edit in read_edit_binding.read() {
+        let device = binding_device(&binding_edit.input);
+        if let Some(inputs) = input_map.get_mut(&binding_edit.action) {
+            inputs.retain(|input| binding_device(input) != device);
+        }
+        input_map.insert(binding_edit.action, binding_edit.input.clone());
+        is_edited = true;
+        info!(
+            "{:?} {:?} binding changed to: {}",
+            binding_edit.action,
+            device,
+            binding_label(&binding_edit.input)
+        );
+    }
+
+    if is_edited {
+        write_config(CONTROLS_FILE_NAME, &*input_map);
+    }
+}
+
+pub fn handle_reset_bindings(
+    mut input_map: ResMut<InputMap<InputAction>>,
+    mut read_reset_bindings: EventReader<ResetBindings>,
+) {
+    if read_reset_bindings.read().count() == 0 {
+        return;
+    }
+
+    *input_map = InputAction::default_input_map();
+    write_config(CONTROLS_FILE_NAME, &*input_map);
+    info!("Controls restored to default bindings");
+}
+
 fn activate_gamepad(
     mut next_input_state: ResMut<NextState<ActiveInput>>,
     mut read_gamepad: EventReader<GamepadEvent>,
@@ -1,3 +1,6 @@
+pub mod accessibility;
 pub mod config_manager;
 pub mod controls;
+pub mod monitors;
+pub mod settings;
 pub mod windows;
@@ -0,0 +1,313 @@
+use super::{
+    settings::Settings,
+    windows::{AspectRatio, DisplayMode, ResolutionSettings, VideoSettings},
+};
+
+use bevy::{prelude::*, window::PrimaryWindow, winit::WinitWindows};
+use winit::window::Fullscreen;
+
+pub const FULLSCREEN_RETRY_SECONDS: f32 = 1.0;
+pub const FULLSCREEN_ATTEMPTS: u32 = 3;
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct MonitorsPlugin;
+
+impl Plugin for MonitorsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Monitors>().add_systems(
+            Update,
+            (enumerate_monitors, apply_fullscreen_monitor).chain(),
+        );
+    }
+}
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoModeInfo {
+    pub size: UVec2,
+    pub refresh_rate_millihertz: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct MonitorInfo {
+    pub name: String,
+    pub video_modes: Vec<VideoModeInfo>,
+}
+
+// monitors in the platform's order, the same order MonitorSelection::Index uses
+#[derive(Resource, Debug, Default)]
+pub struct Monitors {
+    pub is_enumerated: bool,
+    pub monitors: Vec<MonitorInfo>,
+    pub current: Option<usize>,
+}
+
+impl Monitors {
+    // always at least one, so a monitor index can be picked before enumeration
+    pub fn count(&self) -> usize {
+        self.monitors.len().max(1)
+    }
+
+    pub fn label(&self, monitor: usize) -> String {
+        match self.monitors.get(monitor) {
+            Some(info) if !info.name.is_empty() => format!("{}: {}", monitor + 1, info.name),
+            _ => format!("Monitor {}", monitor + 1),
+        }
+    }
+
+    // distinct sizes the monitor reports, smallest first
+    //  falls back to the built-in list when the platform reports nothing
+    pub fn resolutions(
+        &self,
+        monitor: usize,
+        fallback: &ResolutionSettings,
+    ) -> Vec<(Vec2, AspectRatio)> {
+        let mut sizes: Vec<UVec2> = self
+            .monitors
+            .get(monitor)
+            .map(|info| info.video_modes.iter().map(|mode| mode.size).collect())
+            .unwrap_or_default();
+        sizes.sort_by_key(|size| (size.x, size.y));
+        sizes.dedup();
+
+        if sizes.is_empty() {
+            return fallback.options();
+        }
+
+        sizes
+            .into_iter()
+            .map(|size| {
+                let resolution = size.as_vec2();
+                (resolution, AspectRatio::nearest(resolution))
+            })
+            .collect()
+    }
+
+    // refresh rates the monitor offers at this size, highest first
+    pub fn refresh_rates(&self, monitor: usize, resolution: Vec2) -> Vec<u32> {
+        let Some(info) = self.monitors.get(monitor) else {
+            return Vec::new();
+        };
+
+        let mut rates: Vec<u32> = info
+            .video_modes
+            .iter()
+            .filter(|mode| mode.size.as_vec2() == resolution)
+            .map(|mode| mode.refresh_rate_millihertz)
+            .collect();
+        rates.sort_unstable_by(|a, b| b.cmp(a));
+        rates.dedup();
+        rates
+    }
+}
+
+// display mode, monitor, resolution and refresh rate last handed to winit
+pub type FullscreenChoice = (DisplayMode, usize, Vec2, Option<u32>);
+
+// progress of the current choice, a new choice starts over with fresh attempts
+#[derive(Debug, Default)]
+pub struct FullscreenAttempt {
+    pub choice: Option<FullscreenChoice>,
+    pub attempts: u32,
+    pub is_done: bool,
+    pub retry: Timer,
+}
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// None = the highest refresh rate the monitor offers
+pub fn refresh_rate_label(refresh_rate_millihertz: Option<u32>) -> String {
+    match refresh_rate_millihertz {
+        None => "Highest".to_string(),
+        Some(millihertz) if millihertz % 1000 == 0 => format!("{} Hz", millihertz / 1000),
+        Some(millihertz) => format!("{:.2} Hz", millihertz as f32 / 1000.0),
+    }
+}
+
+// the winit fullscreen for the chosen monitor, exclusive fullscreen also picks its video mode
+pub fn fullscreen_target(
+    winit_window: &winit::window::Window,
+    video: &VideoSettings,
+) -> Option<Fullscreen> {
+    let Some(monitor) = winit_window
+        .available_monitors()
+        .nth(video.monitor)
+        .or_else(|| winit_window.current_monitor())
+    else {
+        warn!(
+            "Could not find monitor {}, keeping fullscreen as is.",
+            video.monitor
+        );
+        return None;
+    };
+
+    if video.display_mode == DisplayMode::BorderlessFullscreen {
+        return Some(Fullscreen::Borderless(Some(monitor)));
+    }
+
+    let size = video.resolution.as_uvec2();
+    let video_mode = monitor
+        .video_modes()
+        .filter(|mode| mode.size().width == size.x && mode.size().height == size.y)
+        .filter(|mode| {
+            video
+                .refresh_rate_millihertz
+                .is_none_or(|rate| mode.refresh_rate_millihertz() == rate)
+        })
+        .max_by_key(|mode| mode.refresh_rate_millihertz());
+    if video_mode.is_none() {
+        warn!(
+            "Monitor {} has no {} x {} video mode at {}, keeping the closest one.",
+            video.monitor,
+            size.x,
+            size.y,
+            refresh_rate_label(video.refresh_rate_millihertz)
+        );
+    }
+    video_mode.map(Fullscreen::Exclusive)
+}
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// the winit window may not exist on the first frames, so this retries until it does
+pub fn enumerate_monitors(
+    mut monitors: ResMut<Monitors>,
+    winit_windows: NonSend<WinitWindows>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+) {
+    if monitors.is_enumerated {
+        return;
+    }
+    let Some(winit_window) = windows
+        .get_single()
+        .ok()
+        .and_then(|entity| winit_windows.get_window(entity))
+    else {
+        return;
+    };
+
+    let current_monitor = winit_window.current_monitor();
+    let mut found = Vec::new();
+    let mut current = None;
+    for (index, monitor) in winit_window.available_monitors().enumerate() {
+        if current_monitor.as_ref() == Some(&monitor) {
+            current = Some(index);
+        }
+
+        let mut video_modes: Vec<VideoModeInfo> = monitor
+            .video_modes()
+            .map(|mode| VideoModeInfo {
+                size: UVec2::new(mode.size().width, mode.size().height),
+                refresh_rate_millihertz: mode.refresh_rate_millihertz(),
+            })
+            .collect();
+        video_modes.sort_by_key(|mode| (mode.size.x, mode.size.y, mode.refresh_rate_millihertz));
+        video_modes.dedup();
+
+        found.push(MonitorInfo {
+            name: monitor.name().unwrap_or_default(),
+            video_modes,
+        });
+    }
+
+    if found.iter().all(|info| info.video_modes.is_empty()) {
+        warn!("No monitor video modes reported, using the built-in resolution list.");
+    } else {
+        info!("Found {} monitor(s), current: {:?}", found.len(), current);
+    }
+    monitors.monitors = found;
+    monitors.current = current;
+    monitors.is_enumerated = true;
+}
+
+// bevy's Window has no monitor or refresh rate for fullscreen, and changed_windows only
+//  re-applies its mode when window.mode changes, on the monitor the window is on. once bevy
+//  has switched, the chosen monitor and exclusive video mode are handed to winit here, the
+//  resulting size reaches Window through bevy's own resize events.
+//  winit does not report a refusal, so the switch is checked and asked for again a few times
+pub fn apply_fullscreen_monitor(
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    winit_windows: NonSend<WinitWindows>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    mut attempt: Local<FullscreenAttempt>,
+) {
+    let video = &settings.video;
+    let choice = (
+        video.display_mode,
+        video.monitor,
+        video.resolution,
+        video.refresh_rate_millihertz,
+    );
+    if attempt.choice != Some(choice) {
+        *attempt = FullscreenAttempt {
+            choice: Some(choice),
+            ..default()
+        };
+    }
+    if attempt.is_done {
+        return;
+    }
+
+    if video.display_mode == DisplayMode::Windowed {
+        // windowed placement is handled by Window::position
+        attempt.is_done = true;
+        return;
+    }
+    let Ok(window_entity) = windows.get_single() else {
+        return;
+    };
+    let Some(winit_window) = winit_windows.get_window(window_entity) else {
+        return;
+    };
+
+    let Some(current_fullscreen) = winit_window.fullscreen() else {
+        // bevy has not switched to fullscreen yet
+        return;
+    };
+    let Some(target) = fullscreen_target(winit_window, video) else {
+        attempt.is_done = true;
+        return;
+    };
+    if current_fullscreen == target {
+        if attempt.attempts > 0 {
+            info!(
+                "Fullscreen moved to monitor {} at {}",
+                video.monitor,
+                refresh_rate_label(video.refresh_rate_millihertz)
+            );
+        }
+        attempt.is_done = true;
+        return;
+    }
+
+    // some platforms switch asynchronously, give the last request time to land
+    if attempt.attempts > 0 && !attempt.retry.tick(time.delta()).finished() {
+        return;
+    }
+    if attempt.attempts >= FULLSCREEN_ATTEMPTS {
+        warn!(
+            "Fullscreen on monitor {} at {} was refused {} times, giving up and keeping {:?}.",
+            video.monitor,
+            refresh_rate_label(video.refresh_rate_millihertz),
+            attempt.attempts,
+            current_fullscreen
+        );
+        attempt.is_done = true;
+        return;
+    }
+    if attempt.attempts > 0 {
+        warn!(
+            "Fullscreen on monitor {} was not applied, asking again ({}/{}).",
+            video.monitor,
+            attempt.attempts + 1,
+            FULLSCREEN_ATTEMPTS
+        );
+    }
+
+    winit_window.set_fullscreen(Some(target));
+    attempt.attempts += 1;
+    attempt.retry = Timer::from_seconds(FULLSCREEN_RETRY_SECONDS, TimerMode::Once);
+}
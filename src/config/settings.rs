@@ -0,0 +1,126 @@
+use super::{accessibility::AccessibilitySettings, windows::VideoSettings};
+use crate::audio::audio_buses::AudioSettings;
+
+use bevy::{asset::ron, prelude::*};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use std::{fs, path::PathBuf};
+
+pub const APP_DIR_NAME: &str = "bevy_core_lab";
+pub const SETTINGS_FILE_NAME: &str = "settings.ron";
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .add_event::<SaveSettings>()
+            .add_systems(PreStartup, load_settings)
+            .add_systems(Update, handle_save_settings);
+    }
+}
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// every user facing option that is written to / read from the settings file
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Settings {
+    pub video: VideoSettings,
+    pub audio: AudioSettings,
+    pub accessibility: AccessibilitySettings,
+}
+
+// events ----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Event)]
+pub struct SaveSettings;
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// platform config directory, ie: ~/.config/bevy_core_lab on linux
+pub fn config_path(file_name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME).join(file_name))
+}
+
+// None = file missing or invalid, the caller keeps its defaults
+pub fn read_config<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    let Some(path) = config_path(file_name) else {
+        warn!(
+            "No config directory found, using defaults for {}.",
+            file_name
+        );
+        return None;
+    };
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) => {
+            warn!(
+                "Could not read config file {:?}, using defaults: {}",
+                path, error
+            );
+            return None;
+        }
+    };
+
+    match ron::from_str::<T>(&contents) {
+        Ok(config) => {
+            info!("Config loaded from {:?}", path);
+            Some(config)
+        }
+        Err(error) => {
+            warn!("Invalid config file {:?}, using defaults: {}", path, error);
+            None
+        }
+    }
+}
+
+pub fn write_config<T: Serialize>(file_name: &str, config: &T) {
+    let Some(path) = config_path(file_name) else {
+        warn!("No config directory found, {} was not saved.", file_name);
+        return;
+    };
+
+    let contents = match ron::ser::to_string_pretty(config, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            error!("Could not serialize {}: {}", file_name, error);
+            return;
+        }
+    };
+
+    if let Some(dir) = path.parent() {
+        if let Err(error) = fs::create_dir_all(dir) {
+            error!("Could not create config directory {:?}: {}", dir, error);
+            return;
+        }
+    }
+
+    match fs::write(&path, contents) {
+        Ok(()) => info!("Config saved to {:?}", path),
+        Err(error) => error!("Could not write config file {:?}: {}", path, error),
+    }
+}
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn load_settings(mut settings: ResMut<Settings>) {
+    if let Some(loaded_settings) = read_config::<Settings>(SETTINGS_FILE_NAME) {
+        *settings = loaded_settings;
+    }
+}
+
+pub fn handle_save_settings(
+    settings: Res<Settings>,
+    mut read_save_settings: EventReader<SaveSettings>,
+) {
+    // several edits in the same frame only need to be written once
+    if read_save_settings.read().count() == 0 {
+        return;
+    }
+
+    write_config(SETTINGS_FILE_NAME, &*settings);
+}
@@ -1,3 +1,4 @@
+use super::settings::{SaveSettings, Settings};
 use crate::{
     states::app_state::AppState,
     style::{style_splash::SPLASH_CLEAR_COLOR, style_ui::NORMAL_CLEAR_COLOR},
@@ -6,9 +7,10 @@ use crate::{
 use bevy::{
     core::FrameCount,
     prelude::*,
-    window::{PresentMode, WindowMode, WindowResolution},
+    window::{MonitorSelection, PresentMode, WindowMode, WindowPosition, WindowResolution},
 };
 use bevy_framepace::{FramepacePlugin, Limiter};
+use serde::{Deserialize, Serialize};
 
 // plugins ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -23,21 +25,27 @@ impl Plugin for WindowsPlugin {
             .init_resource::<FramerateSettings>()
             .add_event::<EditResolution>()
             .add_event::<EditVsync>()
-            .add_event::<EditFullscreen>()
+            .add_event::<EditDisplayMode>()
             .add_event::<EditMsaa>()
             .add_event::<EditFramerate>()
-            .add_systems(PreStartup, setup_window)
+            .add_event::<EditCameraFit>()
+            .add_event::<EditPixelPerfect>()
+            .add_systems(
+                PreStartup,
+                setup_window.after(super::settings::load_settings),
+            )
             .add_systems(OnExit(AppState::Splash), set_normal_clear_color)
             .add_systems(
                 Update,
                 (
                     make_visible.run_if(in_state(AppState::Splash)),
-                    emit_edit_resolution.run_if(in_state(AppState::Options)),
                     handle_edit_resolution.run_if(in_state(AppState::Options)),
                     handle_edit_vsync.run_if(in_state(AppState::Options)),
-                    handle_edit_fullscreen.run_if(in_state(AppState::Options)),
+                    handle_edit_display_mode.run_if(in_state(AppState::Options)),
                     handle_edit_msaa.run_if(in_state(AppState::Options)),
                     handle_edit_framerate.run_if(in_state(AppState::Options)),
+                    handle_edit_camera_fit.run_if(in_state(AppState::Options)),
+                    handle_edit_pixel_perfect.run_if(in_state(AppState::Options)),
                 ),
             );
     }
@@ -45,6 +53,7 @@ impl Plugin for WindowsPlugin {
 
 // resources -------------------------------------------------------------------
 // -----------------------------------------------------------------------------
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum AspectRatio {
     _4_3,  // _16_12
     _5_4,  // _15_12    most restrictive width
@@ -54,6 +63,118 @@ pub enum AspectRatio {
            // design game for _15_9 ratio and all resolutions will work
 }
 
+impl AspectRatio {
+    // closest named ratio, for resolutions reported by the monitor
+    pub fn nearest(resolution: Vec2) -> AspectRatio {
+        let ratio = resolution.x / resolution.y.max(1.0);
+        [
+            (AspectRatio::_4_3, 4.0 / 3.0),
+            (AspectRatio::_5_4, 5.0 / 4.0),
+            (AspectRatio::_8_5, 8.0 / 5.0),
+            (AspectRatio::_16_9, 16.0 / 9.0),
+            (AspectRatio::_21_9, 21.0 / 9.0),
+        ]
+        .into_iter()
+        .min_by(|(_, a), (_, b)| (a - ratio).abs().total_cmp(&(b - ratio).abs()))
+        .map(|(aspect_ratio, _)| aspect_ratio)
+        .unwrap()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayMode {
+    Windowed,
+    BorderlessFullscreen,
+    ExclusiveFullscreen, // uses the monitor video mode matching the resolution
+}
+
+impl DisplayMode {
+    pub fn next(&self) -> DisplayMode {
+        match self {
+            DisplayMode::Windowed => DisplayMode::BorderlessFullscreen,
+            DisplayMode::BorderlessFullscreen => DisplayMode::ExclusiveFullscreen,
+            DisplayMode::ExclusiveFullscreen => DisplayMode::Windowed,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "Windowed",
+            DisplayMode::BorderlessFullscreen => "Borderless",
+            DisplayMode::ExclusiveFullscreen => "Exclusive",
+        }
+    }
+
+    // SizedFullscreen so the window resolution picks the video mode
+    //  the refresh rate is applied afterwards by monitors::apply_fullscreen_monitor()
+    pub fn window_mode(&self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::BorderlessFullscreen => WindowMode::BorderlessFullscreen,
+            DisplayMode::ExclusiveFullscreen => WindowMode::SizedFullscreen,
+        }
+    }
+}
+
+// how the cameras fit the 15:9 safe area into a window of any size, see game/cameras.rs
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraFitPolicy {
+    Expand,       // show more of the world along the longer axis, no bars
+    Letterbox,    // bars above and below on narrow windows, expand on wide ones
+    Pillarbox,    // bars left and right on wide windows, expand on narrow ones
+    IntegerScale, // whole pixel multiples of the safe area, bars on every side
+}
+
+impl CameraFitPolicy {
+    pub fn next(&self) -> CameraFitPolicy {
+        match self {
+            CameraFitPolicy::Expand => CameraFitPolicy::Letterbox,
+            CameraFitPolicy::Letterbox => CameraFitPolicy::Pillarbox,
+            CameraFitPolicy::Pillarbox => CameraFitPolicy::IntegerScale,
+            CameraFitPolicy::IntegerScale => CameraFitPolicy::Expand,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CameraFitPolicy::Expand => "Expand",
+            CameraFitPolicy::Letterbox => "Letterbox",
+            CameraFitPolicy::Pillarbox => "Pillarbox",
+            CameraFitPolicy::IntegerScale => "Integer Scale",
+        }
+    }
+}
+
+// how the stage is drawn, pixel perfect renders it at the safe area size, see game/cameras.rs
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelPerfectMode {
+    Off,      // stage drawn straight to the window at full resolution
+    Snapped,  // low resolution stage, the camera moves a whole stage pixel at a time
+    Smoothed, // low resolution stage, the sub-pixel camera offset is applied when upscaling
+}
+
+impl PixelPerfectMode {
+    pub fn next(&self) -> PixelPerfectMode {
+        match self {
+            PixelPerfectMode::Off => PixelPerfectMode::Snapped,
+            PixelPerfectMode::Snapped => PixelPerfectMode::Smoothed,
+            PixelPerfectMode::Smoothed => PixelPerfectMode::Off,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PixelPerfectMode::Off => "Off",
+            PixelPerfectMode::Snapped => "Snapped",
+            PixelPerfectMode::Smoothed => "Smoothed",
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self != PixelPerfectMode::Off
+    }
+}
+
 #[derive(Resource)]
 pub struct ResolutionSettings {
     pub _1024_768: Vec2,  // 4:3          (XGA - Apple iPad)
@@ -99,9 +220,75 @@ impl Default for ResolutionSettings {
     }
 }
 
+impl ResolutionSettings {
+    // every resolution in the order the video options picker cycles through them
+    //  only used when the monitor reports no video modes, see monitors::Monitors
+    pub fn options(&self) -> Vec<(Vec2, AspectRatio)> {
+        vec![
+            (self._1024_768, AspectRatio::_4_3),
+            (self._1280_1024, AspectRatio::_5_4),
+            (self._1280_720, AspectRatio::_16_9),
+            (self._1280_800, AspectRatio::_8_5),
+            (self._1360_768, AspectRatio::_16_9),
+            (self._1366_768, AspectRatio::_16_9),
+            (self._1440_900, AspectRatio::_8_5),
+            (self._1600_900, AspectRatio::_16_9),
+            (self._1680_1050, AspectRatio::_8_5),
+            (self._1920_1200, AspectRatio::_8_5),
+            (self._1920_1080, AspectRatio::_16_9),
+            (self._2560_1440, AspectRatio::_16_9),
+            (self._2560_1600, AspectRatio::_8_5),
+            (self._2560_1080, AspectRatio::_21_9),
+            (self._2880_1800, AspectRatio::_8_5),
+            (self._3440_1440, AspectRatio::_21_9),
+            (self._3840_2160, AspectRatio::_16_9),
+        ]
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum FramerateLimiter {
+    Auto,
+    Manual,
+    Off,
+}
+
+// video options persisted in the settings file, applied in setup_window()
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct VideoSettings {
+    pub resolution: Vec2,
+    pub aspect_ratio: AspectRatio,
+    pub vsync: bool,
+    pub display_mode: DisplayMode,
+    pub monitor: usize, // index in the platform's monitor list
+    pub refresh_rate_millihertz: Option<u32>, // exclusive fullscreen only, None = highest
+    pub msaa_samples: u32,
+    pub framerate_limiter: FramerateLimiter,
+    pub camera_fit: CameraFitPolicy, // replaced by integer scaling while pixel perfect
+    pub pixel_perfect: PixelPerfectMode,
+}
+
+impl Default for VideoSettings {
+    fn default() -> VideoSettings {
+        VideoSettings {
+            resolution: Vec2::new(2560.0, 1440.0),
+            aspect_ratio: AspectRatio::_16_9,
+            vsync: true,
+            display_mode: DisplayMode::Windowed,
+            monitor: 0,
+            refresh_rate_millihertz: None,
+            msaa_samples: 1,
+            framerate_limiter: FramerateLimiter::Auto,
+            camera_fit: CameraFitPolicy::Expand,
+            pixel_perfect: PixelPerfectMode::Off,
+        }
+    }
+}
+
 #[derive(Resource)]
 pub struct FramerateSettings {
-    manual_fps: f64,
+    pub manual_fps: f64,
 }
 
 impl Default for FramerateSettings {
@@ -122,7 +309,11 @@ pub struct EditResolution {
 pub struct EditVsync;
 
 #[derive(Event)]
-pub struct EditFullscreen;
+pub struct EditDisplayMode {
+    pub display_mode: DisplayMode,
+    pub monitor: usize,
+    pub refresh_rate_millihertz: Option<u32>,
+}
 
 #[derive(Event)]
 pub struct EditMsaa;
@@ -130,27 +321,62 @@ pub struct EditMsaa;
 #[derive(Event)]
 pub struct EditFramerate;
 
+#[derive(Event)]
+pub struct EditCameraFit;
+
+#[derive(Event)]
+pub struct EditPixelPerfect;
+
 // systems ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 pub fn setup_window(
+    mut commands: Commands,
     mut windows: Query<&mut Window>,
-    resolution_settings: Res<ResolutionSettings>,
+    settings: Res<Settings>,
+    limiter_setting: Res<FramerateSettings>,
     mut framerate: ResMut<bevy_framepace::FramepaceSettings>,
 ) {
-    // TODO: replace hard coded default values with saved options in external file
     let mut window = windows.single_mut();
+    let video = &settings.video;
 
     window.title = "Bevy Core Lab".into();
-    window.present_mode = PresentMode::AutoVsync;
     window.resizable = false;
 
     // window.cursor.visible = false;
 
-    let default_res = resolution_settings._2560_1440;
-    window.resolution.set(default_res.x, default_res.y);
+    window.present_mode = if video.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    window.mode = video.display_mode.window_mode();
+    window.position = WindowPosition::Centered(MonitorSelection::Index(video.monitor));
+
+    let resolution = if video.resolution.min_element() > 0.0 {
+        video.resolution
+    } else {
+        warn!(
+            "Invalid saved resolution {:?}, using default resolution.",
+            video.resolution
+        );
+        VideoSettings::default().resolution
+    };
+    window.resolution.set(resolution.x, resolution.y);
     WindowResolution::set_scale_factor_override(&mut window.resolution, Some(1.0));
 
-    framerate.limiter = Limiter::Auto;
+    match msaa_from_samples(video.msaa_samples) {
+        Some(msaa) => commands.insert_resource(msaa),
+        None => warn!(
+            "Invalid saved MSAA sample count {:?}, using default MSAA setting.",
+            video.msaa_samples
+        ),
+    }
+
+    framerate.limiter = match video.framerate_limiter {
+        FramerateLimiter::Auto => Limiter::Auto,
+        FramerateLimiter::Manual => Limiter::from_framerate(limiter_setting.manual_fps),
+        FramerateLimiter::Off => Limiter::Off,
+    };
 }
 
 pub fn set_normal_clear_color(mut clear_color: ResMut<ClearColor>) {
@@ -167,13 +393,18 @@ pub fn make_visible(mut windows: Query<&mut Window>, frames: Res<FrameCount>) {
 
 pub fn handle_edit_resolution(
     mut windows: Query<&mut Window>,
+    mut settings: ResMut<Settings>,
     mut read_edit_resolution: EventReader<EditResolution>,
+    mut write_save_settings: EventWriter<SaveSettings>,
 ) {
     for resolution_edit in read_edit_resolution.read() {
         let mut window = windows.single_mut();
         let new_resolution = resolution_edit.resolution;
 
         window.resolution.set(new_resolution.x, new_resolution.y);
+        settings.video.resolution = new_resolution;
+        settings.video.aspect_ratio = resolution_edit.aspect_ratio;
+        write_save_settings.send(SaveSettings);
         info!(
             "Resolution changed to: {:?} x {:?}",
             new_resolution.x, new_resolution.y
@@ -183,7 +414,9 @@ pub fn handle_edit_resolution(
 
 pub fn handle_edit_vsync(
     mut windows: Query<&mut Window>,
+    mut settings: ResMut<Settings>,
     mut read_edit_vsync: EventReader<EditVsync>,
+    mut write_save_settings: EventWriter<SaveSettings>,
 ) {
     for _ in read_edit_vsync.read() {
         let mut window = windows.single_mut();
@@ -195,56 +428,57 @@ pub fn handle_edit_vsync(
             window.present_mode = PresentMode::AutoVsync;
             info!("Window VSync changed to: {:?}", window.present_mode);
         };
+        settings.video.vsync = matches!(window.present_mode, PresentMode::AutoVsync);
+        write_save_settings.send(SaveSettings);
     }
 }
 
-pub fn handle_edit_fullscreen(
+pub fn handle_edit_display_mode(
     mut windows: Query<&mut Window>,
-    mut read_edit_fullscreen: EventReader<EditFullscreen>,
+    mut settings: ResMut<Settings>,
+    mut read_edit_display_mode: EventReader<EditDisplayMode>,
+    mut write_save_settings: EventWriter<SaveSettings>,
 ) {
-    for _ in read_edit_fullscreen.read() {
+    for display_edit in read_edit_display_mode.read() {
         let mut window = windows.single_mut();
 
-        window.mode = if matches!(window.mode, WindowMode::BorderlessFullscreen) {
-            WindowMode::Windowed
-        } else {
-            WindowMode::BorderlessFullscreen
-        };
-        info!("Window Mode changed to: {:?}", window.mode);
+        // fullscreen modes open on the monitor the window is on, so move it there first
+        window.position = WindowPosition::Centered(MonitorSelection::Index(display_edit.monitor));
+        window.mode = display_edit.display_mode.window_mode();
+        info!(
+            "Window Mode changed to: {:?} on monitor {:?}",
+            window.mode, display_edit.monitor
+        );
+        settings.video.display_mode = display_edit.display_mode;
+        settings.video.monitor = display_edit.monitor;
+        settings.video.refresh_rate_millihertz = display_edit.refresh_rate_millihertz;
+        write_save_settings.send(SaveSettings);
     }
 }
 
 pub fn handle_edit_msaa(
     mut commands: Commands,
     current_msaa_setting: Res<Msaa>,
+    mut settings: ResMut<Settings>,
     mut read_edit_msaa: EventReader<EditMsaa>,
+    mut write_save_settings: EventWriter<SaveSettings>,
 ) {
     for _ in read_edit_msaa.read() {
-        match current_msaa_setting.samples() {
-            1 => {
-                // Off = 1
-                commands.insert_resource(Msaa::Sample2);
-                info!("MSAA setting changed to: {:?}", Msaa::Sample2);
-            }
-            2 => {
-                // Sample2 = 2
-                commands.insert_resource(Msaa::Sample4);
-                info!("MSAA setting changed to: {:?}", Msaa::Sample4);
-            }
-            4 => {
-                // Sample4 = 4
-                commands.insert_resource(Msaa::Sample8);
-                info!("MSAA setting changed to: {:?}", Msaa::Sample8);
-            }
-            8 => {
-                // Sample8 = 8
-                commands.insert_resource(Msaa::Off);
-                info!("MSAA setting changed to: {:?}", Msaa::Off);
-            }
+        let new_msaa_setting = match current_msaa_setting.samples() {
+            1 => Msaa::Sample2, // Off = 1
+            2 => Msaa::Sample4, // Sample2 = 2
+            4 => Msaa::Sample8, // Sample4 = 4
+            8 => Msaa::Off,     // Sample8 = 8
             _ => {
-                warn!("Invalid MSAA sample setting detected.")
+                warn!("Invalid MSAA sample setting detected.");
+                continue;
             }
-        }
+        };
+
+        commands.insert_resource(new_msaa_setting);
+        info!("MSAA setting changed to: {:?}", new_msaa_setting);
+        settings.video.msaa_samples = new_msaa_setting.samples();
+        write_save_settings.send(SaveSettings);
     }
 }
 
@@ -252,7 +486,9 @@ pub fn handle_edit_framerate(
     windows: Query<&Window>,
     mut framerate: ResMut<bevy_framepace::FramepaceSettings>,
     limiter_setting: Res<FramerateSettings>,
+    mut settings: ResMut<Settings>,
     mut read_edit_framerate: EventReader<EditFramerate>,
+    mut write_save_settings: EventWriter<SaveSettings>,
 ) {
     for _ in read_edit_framerate.read() {
         let window = windows.single();
@@ -264,6 +500,7 @@ pub fn handle_edit_framerate(
         match framerate.limiter {
             Limiter::Auto => {
                 framerate.limiter = Limiter::from_framerate(limiter_setting.manual_fps);
+                settings.video.framerate_limiter = FramerateLimiter::Manual;
                 info!(
                     "Framerate limiter set to Manual, {:?} fps.",
                     limiter_setting.manual_fps
@@ -271,136 +508,52 @@ pub fn handle_edit_framerate(
             }
             Limiter::Manual(_duration) => {
                 framerate.limiter = Limiter::Off;
+                settings.video.framerate_limiter = FramerateLimiter::Off;
                 info!("Framerate limiter set to Off.");
             }
             Limiter::Off => {
                 framerate.limiter = Limiter::Auto;
+                settings.video.framerate_limiter = FramerateLimiter::Auto;
                 info!("Framerate limiter set to Auto.");
             }
         }
+        write_save_settings.send(SaveSettings);
     }
 }
 
-// -----------------------------------------------------------------------------
-// -----------------------------------------------------------------------------
-// TODO: replace the functions below with proper ui elements for users
-// -----------------------------------------------------------------------------
-pub fn emit_edit_resolution(
-    windows: Query<&mut Window>,
-    resolution_settings: Res<ResolutionSettings>,
-    mut key_input: ResMut<ButtonInput<KeyCode>>,
-    mut write_edit_resolution: EventWriter<EditResolution>,
+pub fn handle_edit_camera_fit(
+    mut settings: ResMut<Settings>,
+    mut read_edit_camera_fit: EventReader<EditCameraFit>,
+    mut write_save_settings: EventWriter<SaveSettings>,
 ) {
-    if key_input.just_pressed(KeyCode::Digit0) {
-        let window = &windows.single().resolution;
-
-        match (window.physical_width(), window.physical_height()) {
-            (1024, 768) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1280_1024,
-                    aspect_ratio: AspectRatio::_5_4,
-                });
-            }
-            (1280, 1024) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1280_720,
-                    aspect_ratio: AspectRatio::_16_9,
-                });
-            }
-            (1280, 720) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1280_800,
-                    aspect_ratio: AspectRatio::_8_5,
-                });
-            }
-            (1280, 800) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1360_768,
-                    aspect_ratio: AspectRatio::_16_9,
-                });
-            }
-            (1360, 768) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1366_768,
-                    aspect_ratio: AspectRatio::_16_9,
-                });
-            }
-            (1366, 768) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1440_900,
-                    aspect_ratio: AspectRatio::_8_5,
-                });
-            }
-            (1440, 900) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1600_900,
-                    aspect_ratio: AspectRatio::_16_9,
-                });
-            }
-            (1600, 900) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1680_1050,
-                    aspect_ratio: AspectRatio::_8_5,
-                });
-            }
-            (1680, 1050) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1920_1200,
-                    aspect_ratio: AspectRatio::_8_5,
-                });
-            }
-            (1920, 1200) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1920_1080,
-                    aspect_ratio: AspectRatio::_16_9,
-                });
-            }
-            (1920, 1080) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._2560_1440,
-                    aspect_ratio: AspectRatio::_16_9,
-                });
-            }
-            (2560, 1440) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._2560_1600,
-                    aspect_ratio: AspectRatio::_8_5,
-                });
-            }
-            (2560, 1600) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._2560_1080,
-                    aspect_ratio: AspectRatio::_21_9,
-                });
-            }
-            (2560, 1080) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._2880_1800,
-                    aspect_ratio: AspectRatio::_8_5,
-                });
-            }
-            (2880, 1800) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._3440_1440,
-                    aspect_ratio: AspectRatio::_21_9,
-                });
-            }
-            (3440, 1440) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._3840_2160,
-                    aspect_ratio: AspectRatio::_16_9,
-                });
-            }
-            (3840, 2160) => {
-                write_edit_resolution.send(EditResolution {
-                    resolution: resolution_settings._1024_768,
-                    aspect_ratio: AspectRatio::_4_3,
-                });
-            }
-            _ => {
-                warn!("Invalid resolution detected.")
-            }
-        }
-        key_input.reset(KeyCode::Digit0);
+    for _ in read_edit_camera_fit.read() {
+        settings.video.camera_fit = settings.video.camera_fit.next();
+        info!("Camera fit changed to: {:?}", settings.video.camera_fit);
+        write_save_settings.send(SaveSettings);
+    }
+}
+
+pub fn handle_edit_pixel_perfect(
+    mut settings: ResMut<Settings>,
+    mut read_edit_pixel_perfect: EventReader<EditPixelPerfect>,
+    mut write_save_settings: EventWriter<SaveSettings>,
+) {
+    for _ in read_edit_pixel_perfect.read() {
+        settings.video.pixel_perfect = settings.video.pixel_perfect.next();
+        info!(
+            "Pixel perfect changed to: {:?}",
+            settings.video.pixel_perfect
+        );
+        write_save_settings.send(SaveSettings);
+    }
+}
+
+fn msaa_from_samples(samples: u32) -> Option<Msaa> {
+    match samples {
+        1 => Some(Msaa::Off),
+        2 => Some(Msaa::Sample2),
+        4 => Some(Msaa::Sample4),
+        8 => Some(Msaa::Sample8),
+        _ => None,
     }
 }
@@ -0,0 +1,82 @@
+use crate::{
+    states::{
+        app_state::{is_returning_to_origin, AppState},
+        data_state::DataState,
+    },
+    style::{style_fonts::FontAssets, style_ui::UiAssets},
+    ui::menus::MenuAssets,
+};
+
+use bevy::prelude::*;
+use bevy_asset_loader::prelude::*;
+use iyes_progress::ProgressPlugin;
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct AssetCollectionsPlugin;
+
+impl Plugin for AssetCollectionsPlugin {
+    fn build(&self, app: &mut App) {
+        // iyes_progress moves each loading state on to Idle once its collections are ready
+        app.add_plugins((
+            ProgressPlugin::new(DataState::Loading).continue_to(DataState::Idle),
+            ProgressPlugin::new(DataState::LoadingGame).continue_to(DataState::Idle),
+        ))
+        .add_loading_state(
+            LoadingState::new(DataState::Loading)
+                .load_collection::<FontAssets>()
+                .load_collection::<UiAssets>()
+                .load_collection::<MenuAssets>()
+                .load_collection::<ShaderAssets>(),
+        )
+        .add_loading_state(
+            LoadingState::new(DataState::LoadingGame).load_collection::<GameAssets>(),
+        )
+        .add_systems(
+            OnEnter(AppState::Game),
+            load_game_assets.run_if(not(is_returning_to_origin)),
+        );
+    }
+}
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// shaders referenced by ui and transition materials, compiled before first use
+//  the handles are only held so the shaders stay loaded, materials reference them by path
+#[derive(AssetCollection, Resource)]
+pub struct ShaderAssets {
+    #[asset(path = "shaders/fragment/ui_normal_button.wgsl")]
+    _ui_normal_button: Handle<Shader>,
+    #[asset(path = "shaders/fragment/ui_focused_button.wgsl")]
+    _ui_focused_button: Handle<Shader>,
+    #[asset(path = "shaders/fragment/transition_fade_color.wgsl")]
+    _transition_fade_color: Handle<Shader>,
+    #[asset(path = "shaders/fragment/transition_shape.wgsl")]
+    _transition_shape: Handle<Shader>,
+    // imported by transition_shape.wgsl
+    #[asset(path = "shaders/utils", collection(typed))]
+    _shader_utils: Vec<Handle<Shader>>,
+}
+
+// assets only needed once a profile is in game, loaded behind the loading screen
+//  the stage material shaders are compiled here, so the first sprite using one does not wait
+#[derive(AssetCollection, Resource)]
+pub struct GameAssets {
+    #[asset(path = "shaders/fragment/game_outline.wgsl")]
+    _game_outline: Handle<Shader>,
+    #[asset(path = "shaders/fragment/game_hit_flash.wgsl")]
+    _game_hit_flash: Handle<Shader>,
+    #[asset(path = "shaders/fragment/game_dissolve.wgsl")]
+    _game_dissolve: Handle<Shader>,
+    #[asset(path = "shaders/fragment/game_palette_swap.wgsl")]
+    _game_palette_swap: Handle<Shader>,
+    #[asset(path = "shaders/fragment/game_silhouette.wgsl")]
+    _game_silhouette: Handle<Shader>,
+}
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn load_game_assets(mut next_data_state: ResMut<NextState<DataState>>) {
+    next_data_state.set(DataState::LoadingGame);
+    info!("DataState change to: {:?}", DataState::LoadingGame);
+}
@@ -0,0 +1,17 @@
+use super::{asset_collections, save_slots, world_snapshot};
+
+use bevy::prelude::*;
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct DataManagerPlugin;
+
+impl Plugin for DataManagerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            asset_collections::AssetCollectionsPlugin,
+            save_slots::SaveSlotsPlugin,
+            world_snapshot::WorldSnapshotPlugin,
+        ));
+    }
+}
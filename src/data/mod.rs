@@ -0,0 +1,4 @@
+pub mod asset_collections;
+pub mod data_manager;
+pub mod save_slots;
+pub mod world_snapshot;
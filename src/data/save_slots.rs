@@ -0,0 +1,259 @@
+use crate::{
+    config::settings::APP_DIR_NAME,
+    states::{
+        app_state::{is_game_suspended, is_returning_to_origin, AppState},
+        profile_state::ProfileState,
+    },
+};
+
+use bevy::{app::AppExit, asset::ron, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const SAVE_VERSION: u32 = 1;
+pub const SAVES_DIR_NAME: &str = "saves";
+pub const METADATA_FILE_NAME: &str = "meta.ron";
+pub const PLAYTIME_WRITE_SECONDS: f64 = 60.0; // the playtime label shows whole minutes
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct SaveSlotsPlugin;
+
+impl Plugin for SaveSlotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveSlots>()
+            .add_systems(Startup, read_save_slots)
+            .add_systems(
+                OnExit(AppState::Game),
+                close_save_slot.run_if(not(is_game_suspended)),
+            )
+            .add_systems(
+                Update,
+                (
+                    // ProfileState and AppState are both applied by the time Update runs
+                    open_save_slot.run_if(
+                        state_changed::<AppState>
+                            .and_then(in_state(AppState::Game))
+                            .and_then(not(is_returning_to_origin)),
+                    ),
+                    track_playtime.run_if(in_state(AppState::Game)),
+                ),
+            )
+            // the app closes without leaving AppState::Game, ie: the window is closed in game
+            .add_systems(
+                Last,
+                close_save_slot.run_if(
+                    on_event::<AppExit>()
+                        .and_then(in_state(AppState::Game).or_else(is_game_suspended)),
+                ),
+            );
+    }
+}
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// header written next to each profile's save data, timestamps are unix seconds
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaveMetadata {
+    pub save_version: u32,
+    pub created: u64,
+    pub last_played: u64,
+    pub playtime_secs: f64,
+}
+
+impl SaveMetadata {
+    pub fn new() -> SaveMetadata {
+        let now = unix_now();
+
+        SaveMetadata {
+            save_version: SAVE_VERSION,
+            created: now,
+            last_played: now,
+            playtime_secs: 0.0,
+        }
+    }
+
+    pub fn playtime_label(&self) -> String {
+        let minutes = (self.playtime_secs / 60.0) as u64;
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+
+    pub fn last_played_label(&self) -> String {
+        format!("Last played {}", elapsed_label(self.last_played))
+    }
+
+    pub fn created_label(&self) -> String {
+        format!("Created {}", elapsed_label(self.created))
+    }
+}
+
+impl Default for SaveMetadata {
+    fn default() -> SaveMetadata {
+        SaveMetadata::new()
+    }
+}
+
+// None = slot has no save on disk
+#[derive(Resource, Debug, Default)]
+pub struct SaveSlots {
+    pub profile_1: Option<SaveMetadata>,
+    pub profile_2: Option<SaveMetadata>,
+    pub profile_3: Option<SaveMetadata>,
+    pub profile_4: Option<SaveMetadata>,
+}
+
+impl SaveSlots {
+    pub fn get(&self, profile: ProfileState) -> Option<&SaveMetadata> {
+        match profile {
+            ProfileState::Profile1 => self.profile_1.as_ref(),
+            ProfileState::Profile2 => self.profile_2.as_ref(),
+            ProfileState::Profile3 => self.profile_3.as_ref(),
+            ProfileState::Profile4 => self.profile_4.as_ref(),
+        }
+    }
+
+    pub fn get_mut(&mut self, profile: ProfileState) -> &mut Option<SaveMetadata> {
+        match profile {
+            ProfileState::Profile1 => &mut self.profile_1,
+            ProfileState::Profile2 => &mut self.profile_2,
+            ProfileState::Profile3 => &mut self.profile_3,
+            ProfileState::Profile4 => &mut self.profile_4,
+        }
+    }
+}
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// platform data directory, ie: ~/.local/share/bevy_core_lab/saves/profile_1 on linux
+pub fn slot_dir(profile: ProfileState) -> Option<PathBuf> {
+    let slot_name = match profile {
+        ProfileState::Profile1 => "profile_1",
+        ProfileState::Profile2 => "profile_2",
+        ProfileState::Profile3 => "profile_3",
+        ProfileState::Profile4 => "profile_4",
+    };
+
+    dirs::data_dir().map(|dir| dir.join(APP_DIR_NAME).join(SAVES_DIR_NAME).join(slot_name))
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+// coarse time since a unix timestamp, ie: "3h ago"
+pub fn elapsed_label(timestamp: u64) -> String {
+    let elapsed = unix_now().saturating_sub(timestamp);
+
+    match elapsed {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", elapsed / 60),
+        3600..=86399 => format!("{}h ago", elapsed / 3600),
+        _ => format!("{}d ago", elapsed / 86400),
+    }
+}
+
+fn read_metadata(profile: ProfileState) -> Option<SaveMetadata> {
+    let path = slot_dir(profile)?.join(METADATA_FILE_NAME);
+    let contents = fs::read_to_string(&path).ok()?;
+
+    match ron::from_str::<SaveMetadata>(&contents) {
+        Ok(metadata) => Some(metadata),
+        Err(error) => {
+            warn!(
+                "Invalid save metadata {:?}, slot treated as empty: {}",
+                path, error
+            );
+            None
+        }
+    }
+}
+
+pub fn write_metadata(profile: ProfileState, metadata: &SaveMetadata) {
+    let Some(dir) = slot_dir(profile) else {
+        warn!("No data directory found, {:?} was not saved.", profile);
+        return;
+    };
+
+    let contents = match ron::ser::to_string_pretty(metadata, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            error!("Could not serialize save metadata: {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = fs::create_dir_all(&dir) {
+        error!("Could not create save directory {:?}: {}", dir, error);
+        return;
+    }
+
+    let path = dir.join(METADATA_FILE_NAME);
+    match fs::write(&path, contents) {
+        Ok(()) => debug!("Save metadata written to {:?}", path),
+        Err(error) => error!("Could not write save metadata {:?}: {}", path, error),
+    }
+}
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn read_save_slots(mut save_slots: ResMut<SaveSlots>) {
+    for profile in [
+        ProfileState::Profile1,
+        ProfileState::Profile2,
+        ProfileState::Profile3,
+        ProfileState::Profile4,
+    ] {
+        *save_slots.get_mut(profile) = read_metadata(profile);
+    }
+}
+
+pub fn open_save_slot(profile: Res<State<ProfileState>>, mut save_slots: ResMut<SaveSlots>) {
+    let slot = save_slots.get_mut(*profile.get());
+    let is_new_save = slot.is_none();
+
+    let metadata = slot.get_or_insert_with(SaveMetadata::new);
+    metadata.last_played = unix_now();
+    write_metadata(*profile.get(), metadata);
+
+    if is_new_save {
+        info!("Created save slot {:?}", profile.get());
+    } else {
+        info!("Opened save slot {:?}", profile.get());
+    }
+}
+
+pub fn track_playtime(
+    time: Res<Time>,
+    profile: Res<State<ProfileState>>,
+    mut save_slots: ResMut<SaveSlots>,
+) {
+    let Some(metadata) = save_slots.get_mut(*profile.get()) else {
+        return;
+    };
+
+    let previous_secs = metadata.playtime_secs;
+    metadata.playtime_secs += time.delta_seconds_f64();
+
+    // written as it goes, so a crash loses at most one period of playtime
+    if (previous_secs / PLAYTIME_WRITE_SECONDS).floor()
+        != (metadata.playtime_secs / PLAYTIME_WRITE_SECONDS).floor()
+    {
+        metadata.last_played = unix_now();
+        write_metadata(*profile.get(), metadata);
+    }
+}
+
+pub fn close_save_slot(profile: Res<State<ProfileState>>, mut save_slots: ResMut<SaveSlots>) {
+    if let Some(metadata) = save_slots.get_mut(*profile.get()) {
+        metadata.last_played = unix_now();
+        write_metadata(*profile.get(), metadata);
+    }
+}
@@ -0,0 +1,136 @@
+use super::save_slots::{slot_dir, write_metadata, SaveSlots};
+use crate::states::{
+    app_state::{is_game_suspended, is_returning_to_origin, AppState},
+    data_state::DataState,
+    profile_state::ProfileState,
+};
+
+use bevy::{asset::ron, ecs::entity::EntityHashMap, prelude::*, scene::serde::SceneDeserializer};
+use serde::de::DeserializeSeed;
+
+use std::fs;
+
+pub const SNAPSHOT_FILE_NAME: &str = "world.scn.ron";
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct WorldSnapshotPlugin;
+
+impl Plugin for WorldSnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Persist>()
+            .add_systems(OnEnter(DataState::Saving), save_world_snapshot)
+            // a game suspended for Options keeps its world, see AppStateOrigin
+            .add_systems(
+                OnExit(AppState::Game),
+                despawn_persistent_entities.run_if(not(is_game_suspended)),
+            )
+            .add_systems(
+                Update,
+                // ProfileState and AppState are both applied by the time Update runs
+                load_world_snapshot.run_if(
+                    state_changed::<AppState>
+                        .and_then(in_state(AppState::Game))
+                        .and_then(not(is_returning_to_origin)),
+                ),
+            );
+    }
+}
+
+// components ------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// entities marked Persist are written to the active profile's slot on DataState::Saving
+//  only components registered with #[reflect(Component)] are included in the snapshot
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Persist;
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn save_world_snapshot(world: &mut World) {
+    let profile = *world.resource::<State<ProfileState>>().get();
+
+    let mut persist_query = world.query_filtered::<Entity, With<Persist>>();
+    let persist_entities: Vec<Entity> = persist_query.iter(world).collect();
+
+    let scene = DynamicSceneBuilder::from_world(world)
+        .extract_entities(persist_entities.into_iter())
+        .build();
+
+    match scene.serialize_ron(world.resource::<AppTypeRegistry>()) {
+        Ok(contents) => write_snapshot(profile, contents),
+        Err(error) => error!("Could not serialize world snapshot: {}", error),
+    }
+
+    // keep the slot header in sync with the snapshot it describes
+    if let Some(metadata) = world.resource::<SaveSlots>().get(profile) {
+        write_metadata(profile, metadata);
+    }
+
+    world
+        .resource_mut::<NextState<DataState>>()
+        .set(DataState::Idle);
+}
+
+pub fn load_world_snapshot(world: &mut World) {
+    let profile = *world.resource::<State<ProfileState>>().get();
+
+    let Some(path) = slot_dir(profile).map(|dir| dir.join(SNAPSHOT_FILE_NAME)) else {
+        return;
+    };
+    let Ok(contents) = fs::read_to_string(&path) else {
+        debug!("No world snapshot found for {:?}", profile);
+        return;
+    };
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let scene = {
+        let type_registry = type_registry.read();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &type_registry,
+        };
+
+        ron::de::Deserializer::from_str(&contents)
+            .map_err(|error| error.to_string())
+            .and_then(|mut deserializer| {
+                scene_deserializer
+                    .deserialize(&mut deserializer)
+                    .map_err(|error| error.to_string())
+            })
+    };
+
+    match scene {
+        Ok(scene) => match scene.write_to_world(world, &mut EntityHashMap::default()) {
+            Ok(()) => info!("World snapshot loaded from {:?}", path),
+            Err(error) => error!("Could not spawn world snapshot {:?}: {}", path, error),
+        },
+        Err(error) => error!("Invalid world snapshot {:?}: {}", path, error),
+    }
+}
+
+pub fn despawn_persistent_entities(
+    mut commands: Commands,
+    persist_query: Query<Entity, With<Persist>>,
+) {
+    for persist_entity in persist_query.iter() {
+        commands.entity(persist_entity).despawn_recursive();
+    }
+}
+
+fn write_snapshot(profile: ProfileState, contents: String) {
+    let Some(dir) = slot_dir(profile) else {
+        warn!("No data directory found, world snapshot was not saved.");
+        return;
+    };
+
+    if let Err(error) = fs::create_dir_all(&dir) {
+        error!("Could not create save directory {:?}: {}", dir, error);
+        return;
+    }
+
+    let path = dir.join(SNAPSHOT_FILE_NAME);
+    match fs::write(&path, contents) {
+        Ok(()) => info!("World snapshot saved to {:?}", path),
+        Err(error) => error!("Could not write world snapshot {:?}: {}", path, error),
+    }
+}
@@ -1,41 +1,97 @@
 /* -----------------------------------------------------------------------------
-    Editing window resolution also edits cameras via handle_resolution_edit()
-        for 2D cameras, adjust OrthographicProjection{scaling_mode: Fixed{width: and height:}}
-            (w, h) [ 4:3 ]  = (16, 12)  = (256, 192)
-            (w, h) [ 5:4 ]  = (15, 12)  = (240, 192)
-            (w, h) [ 8:5 ]  = (16, 10)  = (256, 160)
-            (w, h) [ 16:9 ] = (16, 9)   = (256, 144)
-            (w, h) [ 21:9 ] = (21, 9)   = (336, 144)
-        for 3D cameras, adjust transform: Transform::from_translation(_,_,z)
+    CameraRig is the only camera state, apply_camera_rig derives all three cameras from it
+        position, zoom and shake are shared by World and Stage, so they always line up
+        systems edit the rig, never the camera entities
+    The game is designed for a 15:9 safe area of SAFE_AREA world units (240 x 144)
+        CameraFit is recomputed from the window size whenever it or the fit policy changes
+        for 2D cameras, OrthographicProjection{scaling_mode: Fixed{width: and height:}}
+            is set to the visible area (ortho_size), always containing the safe area
+        for 3D cameras, the z position shows the same visible height at the z=0 plane (stage)
             FOV = π/4 radians or 45 degrees (bevy engine default)
             z = ( height / 2 ) / ( tan( FOV / 2 ) )
-            z [ 4:3 ]   = 231.765
-            z [ 5:4 ]   = 231.765
-            z [ 8:5 ]   = 193.137
-            z [ 16:9 ]  = 173.823
-            z [ 21:9 ]  = 173.823
+        policies (CameraFitPolicy, persisted in the video settings)
+            Expand          visible area grows along the longer axis of the window
+            Letterbox       narrower than 15:9 gets bars above and below
+            Pillarbox       wider than 15:9 gets bars left and right
+            IntegerScale    safe area drawn at a whole pixel multiple, bars where needed
+        bars are a camera viewport on the World and Stage cameras
+            the UI camera always covers the whole window
+    The World and Stage cameras follow the entity with a CameraTarget, the UI camera never moves
+        the target can move inside the deadzone without moving the cameras
+        cameras lead the target by look_ahead in the direction it is moving
+        critically damped smoothing, smooth_time is roughly the time to catch up
+        the visible area is kept inside the target's bounds when it has them
+    CameraShake adds trauma (0.0 - 1.0) that decays over time
+        shake = trauma² * the screen shake accessibility setting
+        World and Stage get the same noise driven offset and roll, so they stay aligned
+        the offset is kept apart from the rig position, so following is not disturbed by it
+    Zoom is the orthographic scale of the Stage camera, below 1.0 is zoomed in
+        InputAction::Zoom steps the target zoom, holding it repeats the step
+        the zoom eases towards the target, within CameraZoomLevel min and max
+        the 3D FOV is derived from the zoom so the z=0 plane matches the stage
+            top = ( ortho height / 2 ) * zoom
+            FOV = 2 * atan( top / z )
+        CameraFit never touches the zoom, so it is kept across resolution changes
+    Pixel perfect (PixelPerfectMode, persisted in the video settings) draws the stage at 1:1
+        the Stage camera renders to an image of the safe area, one world unit per stage pixel
+            PIXEL_PERFECT_MARGIN extra stage pixels on every side, uncovered by the offset below
+            the camera position is snapped to whole stage pixels
+        the Upscale camera draws that image on a sprite (the canvas) in place of the Stage camera
+            the fit policy is forced to IntegerScale, so stage pixels are whole screen pixels
+        Snapped     the view moves a whole stage pixel at a time, the World camera snaps with it
+        Smoothed    the canvas is shifted by what the snapping removed, in whole screen pixels
+        shake roll turns the canvas, the stage itself is always drawn axis aligned
+    The cameras drawing to the window share one HDR main texture, composited in order
+        only the World camera clears it, the others draw over what is already there
+        only the UI camera tonemaps and writes the texture to the window, the rest Skip
+            so bloom and the post process chain on the UI camera cover the whole frame
+        the stage image is HDR as well, it is tonemapped with everything else by the UI camera
 
       ORDER       RENDER LAYER            CAMERA              CONFIG
       -----       ------------            ------              ------
-        0             1               Background          3D Perspective
-        1             0               Occlusion, Debug    2D Orthographic
-        2             2               UI                  2D Orthographic
+       -1             0               Stage image         2D Orthographic, pixel perfect only
+        0             1               Background          3D Perspective, clears
+        1             0               Occlusion, Debug    2D Orthographic, off when pixel perfect
+        1             3               Upscale             2D Orthographic, pixel perfect only
+        2             2               UI                  2D Orthographic, tonemaps, post process
 ----------------------------------------------------------------------------- */
-use crate::config::windows::{AspectRatio, EditResolution};
-use crate::states::app_state::AppState;
+use crate::config::{
+    controls::InputAction,
+    settings::Settings,
+    windows::{CameraFitPolicy, PixelPerfectMode},
+};
+use crate::graphics::post_process::PostProcessSettings;
+use crate::states::{app_state::AppState, game_state::GameState};
 
 use bevy::{
     core_pipeline::{
         bloom::{BloomPrefilterSettings, BloomSettings},
         tonemapping::Tonemapping,
     },
+    ecs::system::SystemParam,
     prelude::{Projection::Perspective, *},
     render::{
-        camera::{CameraOutputMode, ScalingMode},
-        render_resource::{BlendState, LoadOp},
+        camera::{CameraOutputMode, ClearColorConfig, RenderTarget, ScalingMode, Viewport},
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
         view::RenderLayers,
     },
+    window::{PrimaryWindow, WindowRef},
 };
+use leafwing_input_manager::action_state::ActionState;
+
+use std::f32::consts::FRAC_PI_4;
+
+pub const SAFE_AREA: Vec2 = Vec2::new(240.0, 144.0);
+pub const SHAKE_MAX_OFFSET: Vec2 = Vec2::new(8.0, 6.0); // world units
+pub const SHAKE_MAX_ROLL: f32 = 0.05; // radians
+pub const SHAKE_FREQUENCY: f32 = 18.0;
+pub const TRAUMA_DECAY: f32 = 1.2; // per second
+pub const ZOOM_STEP: f32 = 1.15; // zoom factor per step
+pub const ZOOM_REPEAT_SECONDS: f32 = 0.08;
+pub const ZOOM_SMOOTH_TIME: f32 = 0.12;
+pub const PIXEL_PERFECT_MARGIN: u32 = 1; // stage pixels
 
 // plugins ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -45,18 +101,271 @@ impl Plugin for CamerasPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<CameraTranslation>()
             .add_event::<CameraZoom>()
-            .add_systems(Startup, spawn_cameras)
+            .add_event::<CameraShake>()
+            .init_resource::<CameraRig>()
+            .init_resource::<CameraFollow>()
+            .init_resource::<CameraTrauma>()
+            .init_resource::<CameraZoomLevel>()
+            .add_systems(Startup, (update_camera_fit, spawn_cameras).chain())
             .add_systems(
                 Update,
                 (
-                    handle_edit_resolution.run_if(in_state(AppState::Options)),
-                    handle_translate_camera.run_if(in_state(AppState::Game)),
-                    handle_zoom_camera.run_if(in_state(AppState::Game)),
-                ),
+                    update_camera_fit,
+                    (
+                        handle_translate_camera,
+                        follow_camera_target,
+                        (handle_camera_shake, update_camera_shake).chain(),
+                        (emit_zoom_camera, handle_zoom_camera, animate_zoom_camera).chain(),
+                    )
+                        .chain()
+                        .run_if(in_state(AppState::Game))
+                        .run_if(in_state(GameState::Playing)),
+                    apply_camera_rig.run_if(resource_changed::<CameraRig>),
+                )
+                    .chain(),
             );
     }
 }
 
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// camera sizes derived from the window size and fit policy, see notes at top of file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraFit {
+    pub policy: CameraFitPolicy,
+    pub window_size: UVec2,       // physical pixels
+    pub ortho_size: Vec2,         // world units visible to the World and Stage cameras
+    pub ui_ortho_size: Vec2,      // world units visible to the UI camera, the whole window
+    pub camera_z: f32,            // z position of perspective cameras
+    pub viewport_position: UVec2, // physical pixels, top left of the area inside the bars
+    pub viewport_size: UVec2,
+}
+
+impl Default for CameraFit {
+    fn default() -> CameraFit {
+        CameraFit::new(UVec2::new(2560, 1440), CameraFitPolicy::Expand)
+    }
+}
+
+impl CameraFit {
+    pub fn new(window_size: UVec2, policy: CameraFitPolicy) -> CameraFit {
+        let window = window_size.max(UVec2::ONE).as_vec2();
+        let window_aspect = window.x / window.y;
+        let safe_aspect = SAFE_AREA.x / SAFE_AREA.y;
+
+        // the safe area grown along one axis until it matches the window
+        let expanded = if window_aspect > safe_aspect {
+            Vec2::new(SAFE_AREA.y * window_aspect, SAFE_AREA.y)
+        } else {
+            Vec2::new(SAFE_AREA.x, SAFE_AREA.x / window_aspect)
+        };
+
+        let (ortho_size, viewport_size) = match policy {
+            CameraFitPolicy::Letterbox if window_aspect < safe_aspect => {
+                (SAFE_AREA, Vec2::new(window.x, window.x / safe_aspect))
+            }
+            CameraFitPolicy::Pillarbox if window_aspect > safe_aspect => {
+                (SAFE_AREA, Vec2::new(window.y * safe_aspect, window.y))
+            }
+            CameraFitPolicy::IntegerScale => {
+                // windows smaller than the safe area can only be fitted, not scaled up
+                let fit_scale = (window / SAFE_AREA).min_element();
+                let scale = if fit_scale >= 1.0 {
+                    fit_scale.floor()
+                } else {
+                    fit_scale
+                };
+                (SAFE_AREA, SAFE_AREA * scale)
+            }
+            _ => (expanded, window),
+        };
+
+        let viewport_size = viewport_size
+            .round()
+            .as_uvec2()
+            .clamp(UVec2::ONE, window_size.max(UVec2::ONE));
+        CameraFit {
+            policy,
+            window_size,
+            ortho_size,
+            ui_ortho_size: expanded,
+            camera_z: (ortho_size.y * 0.5) / (FRAC_PI_4 * 0.5).tan(),
+            viewport_position: (window_size.max(UVec2::ONE) - viewport_size) / 2,
+            viewport_size,
+        }
+    }
+
+    // None when there are no bars and cameras render to the whole window
+    pub fn viewport(&self) -> Option<Viewport> {
+        if self.viewport_size == self.window_size {
+            return None;
+        }
+
+        Some(Viewport {
+            physical_position: self.viewport_position,
+            physical_size: self.viewport_size,
+            ..default()
+        })
+    }
+}
+
+// everything the cameras show, see notes at top of file
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct CameraRig {
+    pub position: Vec2, // world units, centre of the view at the z=0 plane
+    pub zoom: f32,
+    pub fit: CameraFit,
+    pub shake_offset: Vec2,
+    pub shake_roll: f32, // radians
+    pub pixel_perfect: PixelPerfectMode,
+}
+
+impl Default for CameraRig {
+    fn default() -> CameraRig {
+        CameraRig {
+            position: Vec2::ZERO,
+            zoom: 1.0,
+            fit: CameraFit::default(),
+            shake_offset: Vec2::ZERO,
+            shake_roll: 0.0,
+            pixel_perfect: PixelPerfectMode::Off,
+        }
+    }
+}
+
+impl CameraRig {
+    // where the World and Stage cameras look, shake included
+    pub fn view_position(&self) -> Vec2 {
+        self.position + self.shake_offset
+    }
+
+    // the stage image camera position on whole stage pixels, and the canvas offset in stage pixels
+    pub fn pixel_snap(&self) -> (Vec2, Vec2) {
+        let stage_pixel = self.zoom; // world units
+        let position = self.view_position() / stage_pixel;
+        let snapped = position.round();
+
+        // screen pixels per stage pixel, whole numbers unless the window is smaller than the safe area
+        let scale = self.fit.viewport_size.as_vec2() / self.fit.ortho_size;
+        let offset = match self.pixel_perfect {
+            PixelPerfectMode::Smoothed => ((position - snapped) * scale).round() / scale,
+            _ => Vec2::ZERO,
+        };
+        (snapped * stage_pixel, offset)
+    }
+
+    // where the view ends up once pixel perfect snapping is applied
+    pub fn shown_position(&self) -> Vec2 {
+        if !self.pixel_perfect.is_enabled() {
+            return self.view_position();
+        }
+
+        let (snapped, offset) = self.pixel_snap();
+        snapped + offset * self.zoom
+    }
+
+    // world units visible to the World and Stage cameras at the z=0 plane
+    pub fn visible_size(&self) -> Vec2 {
+        self.fit.ortho_size * self.zoom
+    }
+
+    pub fn world_transform(&self) -> Transform {
+        Transform::from_translation(self.shown_position().extend(self.fit.camera_z))
+            .with_rotation(Quat::from_rotation_z(self.shake_roll))
+    }
+
+    // top = y coordinate of perspective projection frustum top as it intersects z=0 plane (stage)
+    pub fn world_fov(&self) -> f32 {
+        let top = self.visible_size().y * 0.5;
+        2.0 * (top / self.fit.camera_z).atan()
+    }
+
+    // the 2D camera keeps its own depth, the stage image is never rolled
+    pub fn stage_transform(&self, z: f32) -> Transform {
+        if self.pixel_perfect.is_enabled() {
+            return Transform::from_translation(self.pixel_snap().0.extend(z));
+        }
+
+        Transform::from_translation(self.view_position().extend(z))
+            .with_rotation(Quat::from_rotation_z(self.shake_roll))
+    }
+
+    pub fn stage_scaling_mode(&self) -> ScalingMode {
+        let size = match self.pixel_perfect.is_enabled() {
+            true => stage_image_size().as_vec2(),
+            false => self.fit.ortho_size,
+        };
+        ScalingMode::Fixed {
+            width: size.x,
+            height: size.y,
+        }
+    }
+
+    // the canvas turns the opposite way to the cameras, so the stage rolls with the World camera
+    pub fn canvas_transform(&self) -> Transform {
+        let (_, offset) = self.pixel_snap();
+        Transform::from_rotation(Quat::from_rotation_z(-self.shake_roll))
+            * Transform::from_translation((-offset).extend(0.0))
+    }
+
+    // the Upscale camera shows the safe area of the canvas, in stage pixels
+    pub fn upscale_scaling_mode(&self) -> ScalingMode {
+        ScalingMode::Fixed {
+            width: self.fit.ortho_size.x,
+            height: self.fit.ortho_size.y,
+        }
+    }
+
+    // the UI is never zoomed, moved or shaken
+    pub fn ui_scaling_mode(&self) -> ScalingMode {
+        ScalingMode::Fixed {
+            width: self.fit.ui_ortho_size.x,
+            height: self.fit.ui_ortho_size.y,
+        }
+    }
+}
+
+// the image the Stage camera renders to while pixel perfect
+#[derive(Resource, Debug)]
+pub struct StageImage {
+    pub image: Handle<Image>,
+}
+
+// how the rig is moving towards its target, see follow_camera_target
+#[derive(Resource, Debug, Default)]
+pub struct CameraFollow {
+    pub target: Option<Entity>,
+    pub velocity: Vec2,
+    pub focus: Vec2, // target position once the deadzone has been applied
+    pub look_ahead: Vec2,
+    pub look_ahead_velocity: Vec2,
+    pub last_target_position: Vec2,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct CameraTrauma {
+    pub trauma: f32,
+    pub elapsed: f32, // drives the noise, only advances while shaking
+}
+
+// the zoom the rig is easing towards
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct CameraZoomLevel {
+    pub target: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Default for CameraZoomLevel {
+    fn default() -> CameraZoomLevel {
+        CameraZoomLevel {
+            target: 1.0,
+            min: 0.5,
+            max: 2.0,
+        }
+    }
+}
+
 // components ------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 #[derive(Component)]
@@ -68,6 +377,58 @@ pub struct StageCamera;
 #[derive(Component)]
 pub struct UICamera;
 
+#[derive(Component)]
+pub struct UpscaleCamera;
+
+// the sprite showing the stage image to the Upscale camera
+#[derive(Component)]
+pub struct UpscaleCanvas;
+
+// the parts of each camera apply_camera_rig writes, the filters keep the queries disjoint
+pub type WorldCameraParts = (
+    &'static mut Camera,
+    &'static mut Transform,
+    &'static mut Projection,
+);
+pub type StageCameraParts = (
+    &'static mut Camera,
+    &'static mut Transform,
+    &'static mut OrthographicProjection,
+);
+pub type UpscaleParts = (&'static mut Camera, &'static mut OrthographicProjection);
+pub type CanvasParts = (&'static mut Transform, &'static mut Visibility);
+pub type UICameraOnly = (With<UICamera>, Without<StageCamera>, Without<UpscaleCamera>);
+
+// every camera and canvas apply_camera_rig writes to
+#[derive(SystemParam)]
+pub struct CameraRigQueries<'w, 's> {
+    pub world: Query<'w, 's, WorldCameraParts, (With<WorldCamera>, Without<StageCamera>)>,
+    pub stage: Query<'w, 's, StageCameraParts, (With<StageCamera>, Without<WorldCamera>)>,
+    pub upscale: Query<'w, 's, UpscaleParts, (With<UpscaleCamera>, Without<StageCamera>)>,
+    pub canvas: Query<'w, 's, CanvasParts, (With<UpscaleCanvas>, Without<WorldCamera>)>,
+    pub ui: Query<'w, 's, &'static mut OrthographicProjection, UICameraOnly>,
+}
+
+// the World and Stage cameras follow the entity with this, all distances are in world units
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CameraTarget {
+    pub deadzone: Vec2, // half size of the box the target can move in freely
+    pub smooth_time: f32,
+    pub look_ahead: f32,
+    pub bounds: Option<Rect>, // level bounds, the visible area never leaves them
+}
+
+impl Default for CameraTarget {
+    fn default() -> CameraTarget {
+        CameraTarget {
+            deadzone: Vec2::new(16.0, 12.0),
+            smooth_time: 0.25,
+            look_ahead: 24.0,
+            bounds: None,
+        }
+    }
+}
+
 // events ----------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 
@@ -76,15 +437,80 @@ pub struct CameraTranslation {
     pub position: Vec2,
 }
 
+// trauma is added to what is left of earlier shakes, 1.0 is the strongest shake
+#[derive(Event)]
+pub struct CameraShake {
+    pub trauma: f32,
+}
+
+// one ZOOM_STEP towards or away from the stage
 #[derive(Event)]
 pub struct CameraZoom {
-    is_directed_in: bool,
+    pub is_directed_in: bool,
+}
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// critically damped spring towards target, velocity carries over between frames
+//  Game Programming Gems 4, 1.10
+pub fn smooth_damp(
+    current: Vec2,
+    target: Vec2,
+    velocity: &mut Vec2,
+    smooth_time: f32,
+    delta_seconds: f32,
+) -> Vec2 {
+    let omega = 2.0 / smooth_time.max(0.0001);
+    let x = omega * delta_seconds;
+    let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+
+    let change = current - target;
+    let temp = (*velocity + omega * change) * delta_seconds;
+    *velocity = (*velocity - omega * temp) * decay;
+    target + (change + temp) * decay
+}
+
+// camera position that keeps the visible area inside bounds, centred when it can't fit
+pub fn clamp_to_bounds(position: Vec2, visible_size: Vec2, bounds: Rect) -> Vec2 {
+    let half = visible_size * 0.5;
+    let clamp_axis = |value: f32, min: f32, max: f32, half: f32| {
+        if max - min <= half * 2.0 {
+            (min + max) * 0.5
+        } else {
+            value.clamp(min + half, max - half)
+        }
+    };
+
+    Vec2::new(
+        clamp_axis(position.x, bounds.min.x, bounds.max.x, half.x),
+        clamp_axis(position.y, bounds.min.y, bounds.max.y, half.y),
+    )
+}
+
+// smooth noise in -1.0 - 1.0, each seed gives an unrelated curve
+pub fn shake_noise(seed: f32, t: f32) -> f32 {
+    let hash = |n: f32| ((n * 12.9898 + seed * 78.233).sin() * 43_758.547).fract() * 2.0 - 1.0;
+    let i = t.floor();
+    let f = t - i;
+    let blend = f * f * (3.0 - 2.0 * f);
+    hash(i) + (hash(i + 1.0) - hash(i)) * blend
+}
+
+// the safe area in stage pixels, with the margin the smoothing offset uncovers
+pub fn stage_image_size() -> UVec2 {
+    SAFE_AREA.as_uvec2() + UVec2::splat(PIXEL_PERFECT_MARGIN * 2)
 }
 
 // systems ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
-pub fn spawn_cameras(mut commands: Commands) {
-    // TODO: all cameras currently assume a 16:9 aspect ratio when spawning. replace with options resource / save file
+pub fn spawn_cameras(
+    mut commands: Commands,
+    rig: Res<CameraRig>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    // cameras spawn fitted to the window set up from the settings file
+    //  pixel perfect is set up by apply_camera_rig on the first frame
+
     // MAIN
     // ----------
     commands.spawn((
@@ -95,16 +521,23 @@ pub fn spawn_cameras(mut commands: Commands) {
             camera: Camera {
                 order: 0,
                 hdr: true,
+                viewport: rig.fit.viewport(),
+                output_mode: CameraOutputMode::Skip,
                 ..default()
             },
-            projection: Perspective(PerspectiveProjection { ..default() }),
-            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 173.82)),
+            projection: Perspective(PerspectiveProjection {
+                fov: rig.world_fov(),
+                ..default()
+            }),
+            transform: rig.world_transform(),
+            tonemapping: Tonemapping::None,
             ..default()
         },
     ));
 
     // STAGE
     // -----
+    let stage_bundle = Camera2dBundle::default();
     commands.spawn((
         Name::new("CameraStage"),
         StageCamera,
@@ -113,24 +546,87 @@ pub fn spawn_cameras(mut commands: Commands) {
             camera: Camera {
                 order: 1,
                 hdr: true,
-                msaa_writeback: false,
-                output_mode: CameraOutputMode::Write {
-                    blend_state: Some(BlendState::ALPHA_BLENDING),
-                    color_attachment_load_op: LoadOp::Load,
-                },
+                viewport: rig.fit.viewport(),
+                clear_color: ClearColorConfig::None,
+                output_mode: CameraOutputMode::Skip,
+                ..default()
+            },
+            projection: OrthographicProjection {
+                near: -1.0,
+                scale: rig.zoom,
+                scaling_mode: rig.stage_scaling_mode(),
+                ..default()
+            },
+            transform: rig.stage_transform(stage_bundle.transform.translation.z),
+            tonemapping: Tonemapping::None,
+            ..stage_bundle
+        },
+    ));
+
+    // UPSCALE
+    // -------
+    let size = stage_image_size();
+    let extent = Extent3d {
+        width: size.x,
+        height: size.y,
+        ..default()
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("stage_image"),
+            size: extent,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba16Float,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(extent);
+    let image = images.add(image);
+
+    commands.spawn((
+        Name::new("CameraUpscale"),
+        UpscaleCamera,
+        RenderLayers::from_layers(&[3]),
+        Camera2dBundle {
+            camera: Camera {
+                order: 1,
+                is_active: false,
+                hdr: true,
+                viewport: rig.fit.viewport(),
+                clear_color: ClearColorConfig::None,
+                output_mode: CameraOutputMode::Skip,
                 ..default()
             },
             projection: OrthographicProjection {
                 near: -1.0,
-                scaling_mode: ScalingMode::Fixed {
-                    width: 256.0,
-                    height: 144.0,
-                },
+                scaling_mode: rig.upscale_scaling_mode(),
                 ..default()
             },
+            tonemapping: Tonemapping::None,
             ..default()
         },
     ));
+    commands.spawn((
+        Name::new("UpscaleCanvas"),
+        UpscaleCanvas,
+        RenderLayers::from_layers(&[3]),
+        SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(size.as_vec2()),
+                ..default()
+            },
+            texture: image.clone(),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+    ));
+    commands.insert_resource(StageImage { image });
 
     // UI
     // --
@@ -142,24 +638,19 @@ pub fn spawn_cameras(mut commands: Commands) {
             camera: Camera {
                 order: 2,
                 hdr: true,
-                msaa_writeback: false,
-                output_mode: CameraOutputMode::Write {
-                    blend_state: Some(BlendState::ALPHA_BLENDING),
-                    color_attachment_load_op: LoadOp::Load,
-                },
+                clear_color: ClearColorConfig::None,
                 ..default()
             },
             projection: OrthographicProjection {
                 near: -1.0,
-                scaling_mode: ScalingMode::Fixed {
-                    width: 256.0,
-                    height: 144.0,
-                },
+                scaling_mode: rig.ui_scaling_mode(),
                 ..default()
             },
             tonemapping: Tonemapping::TonyMcMapface,
             ..default()
         },
+        // full screen effects, the UI camera writes the composited frame so they cover everything
+        PostProcessSettings::default(),
         BloomSettings {
             intensity: 0.1,
             prefilter_settings: BloomPrefilterSettings {
@@ -171,136 +662,507 @@ pub fn spawn_cameras(mut commands: Commands) {
     ));
 }
 
-pub fn handle_edit_resolution(
-    mut orthographic_query: Query<&mut OrthographicProjection>,
-    mut perspective_query: Query<&mut Transform, With<Projection>>,
-    mut read_edit_resolution: EventReader<EditResolution>,
+// window size is read every frame, the rig only changes when the fit does
+pub fn update_camera_fit(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+    mut rig: ResMut<CameraRig>,
 ) {
-    for res_edit in read_edit_resolution.read() {
-        //  edit the Fixed Width and Height for orthographic projections
-        //  edit the z position of cameras with perspective projections
-        match &res_edit.aspect_ratio {
-            AspectRatio::_4_3 => {
-                for mut camera2d in orthographic_query.iter_mut() {
-                    camera2d.scaling_mode = ScalingMode::Fixed {
-                        width: 256.0,
-                        height: 192.0,
-                    };
-                }
-                for mut camera3d in perspective_query.iter_mut() {
-                    let temp_translation = camera3d.translation;
-                    camera3d.translation =
-                        Vec3::new(temp_translation.x, temp_translation.y, 231.765);
-                }
-            }
-            AspectRatio::_5_4 => {
-                for mut camera2d in orthographic_query.iter_mut() {
-                    camera2d.scaling_mode = ScalingMode::Fixed {
-                        width: 240.0,
-                        height: 192.0,
-                    };
-                }
-                for mut camera3d in perspective_query.iter_mut() {
-                    let temp_translation = camera3d.translation;
-                    camera3d.translation =
-                        Vec3::new(temp_translation.x, temp_translation.y, 231.765);
-                }
-            }
-            AspectRatio::_8_5 => {
-                for mut camera2d in orthographic_query.iter_mut() {
-                    camera2d.scaling_mode = ScalingMode::Fixed {
-                        width: 256.0,
-                        height: 160.0,
-                    };
-                }
-                for mut camera3d in perspective_query.iter_mut() {
-                    let temp_translation = camera3d.translation;
-                    camera3d.translation =
-                        Vec3::new(temp_translation.x, temp_translation.y, 193.137);
-                }
-            }
-            AspectRatio::_16_9 => {
-                for mut camera2d in orthographic_query.iter_mut() {
-                    camera2d.scaling_mode = ScalingMode::Fixed {
-                        width: 256.0,
-                        height: 144.0,
-                    };
-                }
-                for mut camera3d in perspective_query.iter_mut() {
-                    let temp_translation = camera3d.translation;
-                    camera3d.translation =
-                        Vec3::new(temp_translation.x, temp_translation.y, 173.823);
-                }
-            }
-            AspectRatio::_21_9 => {
-                for mut camera2d in orthographic_query.iter_mut() {
-                    camera2d.scaling_mode = ScalingMode::Fixed {
-                        width: 336.0,
-                        height: 144.0,
-                    };
-                }
-                for mut camera3d in perspective_query.iter_mut() {
-                    let temp_translation = camera3d.translation;
-                    camera3d.translation =
-                        Vec3::new(temp_translation.x, temp_translation.y, 173.823);
-                }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    // stage pixels are only whole screen pixels when integer scaled
+    let pixel_perfect = settings.video.pixel_perfect;
+    let policy = match pixel_perfect.is_enabled() {
+        true => CameraFitPolicy::IntegerScale,
+        false => settings.video.camera_fit,
+    };
+    let window_size = UVec2::new(window.physical_width(), window.physical_height());
+    let fit = CameraFit::new(window_size, policy);
+    if rig.fit == fit && rig.pixel_perfect == pixel_perfect {
+        return;
+    }
+
+    rig.fit = fit;
+    rig.pixel_perfect = pixel_perfect;
+    info!(
+        "Cameras fitted to {:?} window with {:?}: {:?} visible, bars: {:?}",
+        fit.window_size,
+        fit.policy,
+        fit.ortho_size,
+        fit.viewport().is_some()
+    );
+}
+
+// the only system that writes to the cameras
+pub fn apply_camera_rig(
+    rig: Res<CameraRig>,
+    stage_image: Res<StageImage>,
+    mut queries: CameraRigQueries,
+    mut last_layout: Local<Option<(CameraFit, PixelPerfectMode)>>,
+) {
+    let is_pixel_perfect = rig.pixel_perfect.is_enabled();
+
+    // the rig changes every frame the view moves, writing to Camera makes bevy recompute
+    //  its render target, so the layout is only written when the fit or pixel perfect changed
+    let layout = (rig.fit, rig.pixel_perfect);
+    let is_layout_changed = *last_layout != Some(layout);
+    *last_layout = Some(layout);
+
+    for (mut camera, mut transform, projection) in queries.world.iter_mut() {
+        if is_layout_changed {
+            camera.viewport = rig.fit.viewport();
+        }
+        *transform = rig.world_transform();
+        if let Perspective(proj) = projection.into_inner() {
+            proj.fov = rig.world_fov();
+        }
+    }
+
+    for (mut camera, mut transform, mut projection) in queries.stage.iter_mut() {
+        if is_layout_changed {
+            if is_pixel_perfect {
+                // cleared to transparent every frame, the Upscale camera blends it over the World
+                camera.order = -1;
+                camera.target = RenderTarget::Image(stage_image.image.clone());
+                camera.viewport = None;
+                camera.clear_color = ClearColorConfig::Custom(Color::NONE);
+                camera.output_mode = CameraOutputMode::default();
+            } else {
+                // drawn over the World in the shared main texture
+                camera.order = 1;
+                camera.target = RenderTarget::Window(WindowRef::Primary);
+                camera.viewport = rig.fit.viewport();
+                camera.clear_color = ClearColorConfig::None;
+                camera.output_mode = CameraOutputMode::Skip;
             }
+        }
+        *transform = rig.stage_transform(transform.translation.z);
+        projection.scaling_mode = rig.stage_scaling_mode();
+        projection.scale = rig.zoom;
+    }
+
+    for (mut camera, mut projection) in queries.upscale.iter_mut() {
+        if is_layout_changed {
+            camera.is_active = is_pixel_perfect;
+            camera.viewport = rig.fit.viewport();
+        }
+        projection.scaling_mode = rig.upscale_scaling_mode();
+    }
+
+    for (mut transform, mut visibility) in queries.canvas.iter_mut() {
+        *visibility = match is_pixel_perfect {
+            true => Visibility::Visible,
+            false => Visibility::Hidden,
         };
+        *transform = rig.canvas_transform();
+    }
+
+    for mut projection in queries.ui.iter_mut() {
+        projection.scaling_mode = rig.ui_scaling_mode();
     }
 }
 
+// moves the rig straight to a position, a target pulls it back from there
 pub fn handle_translate_camera(
-    mut camera_query: Query<&mut Transform, With<Camera>>,
+    mut rig: ResMut<CameraRig>,
+    mut follow: ResMut<CameraFollow>,
     mut read_translate_camera: EventReader<CameraTranslation>,
 ) {
     for cam_edit in read_translate_camera.read() {
-        for mut camera in camera_query.iter_mut() {
-            camera.translation = Vec3::new(
-                cam_edit.position.x,
-                cam_edit.position.y,
-                camera.translation.z, // don't change the current depth (z) value
-            );
-        }
+        rig.position = cam_edit.position;
+        follow.velocity = Vec2::ZERO;
+    }
+}
+
+// virtual time, so the cameras hold still while the game is paused
+pub fn follow_camera_target(
+    time: Res<Time>,
+    mut rig: ResMut<CameraRig>,
+    mut follow: ResMut<CameraFollow>,
+    target_query: Query<(Entity, &CameraTarget, &GlobalTransform)>,
+) {
+    let Some((target_entity, target, target_transform)) = target_query.iter().next() else {
+        follow.target = None;
+        return;
+    };
+    let target_position = target_transform.translation().truncate();
+    let delta_seconds = time.delta_seconds();
+
+    let visible_size = rig.visible_size();
+    let clamp = |position: Vec2| {
+        target.bounds.map_or(position, |bounds| {
+            clamp_to_bounds(position, visible_size, bounds)
+        })
+    };
+
+    if follow.target != Some(target_entity) {
+        // a new target starts centred, without easing in from wherever the cameras were
+        *follow = CameraFollow {
+            target: Some(target_entity),
+            focus: target_position,
+            last_target_position: target_position,
+            ..default()
+        };
+        rig.position = clamp(target_position);
+        return;
     }
+
+    // the focus only moves once the target pushes against the edge of the deadzone
+    let offset = target_position - follow.focus;
+    follow.focus += offset - offset.clamp(-target.deadzone, target.deadzone);
+
+    // lead in the direction the target is moving, easing back to centre when it stops
+    let moved = target_position - follow.last_target_position;
+    let look_ahead_goal = moved.normalize_or_zero() * target.look_ahead;
+    let mut look_ahead_velocity = follow.look_ahead_velocity;
+    follow.look_ahead = smooth_damp(
+        follow.look_ahead,
+        look_ahead_goal,
+        &mut look_ahead_velocity,
+        target.smooth_time * 2.0,
+        delta_seconds,
+    );
+    follow.look_ahead_velocity = look_ahead_velocity;
+    follow.last_target_position = target_position;
+
+    let goal = clamp(follow.focus + follow.look_ahead);
+    let mut velocity = follow.velocity;
+    let position = smooth_damp(
+        rig.position,
+        goal,
+        &mut velocity,
+        target.smooth_time,
+        delta_seconds,
+    );
+    follow.velocity = velocity;
+    rig.position = clamp(position);
+}
+
+// the mouse wheel gives one step per notch, held triggers and keys repeat
+pub fn emit_zoom_camera(
+    time: Res<Time<Real>>,
+    action_state: Res<ActionState<InputAction>>,
+    mut repeat_cooldown: Local<f32>,
+    mut write_zoom_camera: EventWriter<CameraZoom>,
+) {
+    let value = action_state.clamped_value(&InputAction::Zoom);
+    if value.abs() < 0.1 {
+        // released, the next press steps straight away
+        *repeat_cooldown = 0.0;
+        return;
+    }
+
+    *repeat_cooldown -= time.delta_seconds();
+    if *repeat_cooldown > 0.0 {
+        return;
+    }
+    *repeat_cooldown = ZOOM_REPEAT_SECONDS;
+    write_zoom_camera.send(CameraZoom {
+        is_directed_in: value > 0.0,
+    });
 }
 
 pub fn handle_zoom_camera(
-    mut orthographic_query: Query<&mut OrthographicProjection, Without<UICamera>>,
-    mut perspective_query: Query<(&mut Projection, &Transform)>,
+    mut zoom_level: ResMut<CameraZoomLevel>,
     mut read_zoom_camera: EventReader<CameraZoom>,
 ) {
     for cam_zoom in read_zoom_camera.read() {
-        // will use aspect ratio and scale of 2d cameras to calculate new FOV for 3d cameras
-        let mut aspect_ratio_y = 0.0;
-        let mut new_scale = 0.0;
+        let target = if cam_zoom.is_directed_in {
+            zoom_level.target / ZOOM_STEP // zoom camera IN
+        } else {
+            zoom_level.target * ZOOM_STEP // zoom camera OUT
+        };
+        zoom_level.target = target.clamp(zoom_level.min, zoom_level.max);
+    }
+}
 
-        for mut camera_2d in orthographic_query.iter_mut() {
-            if cam_zoom.is_directed_in {
-                camera_2d.scale += 0.25; // zoom camera IN
-            } else {
-                camera_2d.scale -= 0.25; // zoom camera OUT
-            }
-            camera_2d.scale = camera_2d.scale.clamp(0.25, 5.0);
+// eases in log space, so zooming in and out feel the same
+pub fn animate_zoom_camera(
+    time: Res<Time>,
+    zoom_level: Res<CameraZoomLevel>,
+    mut rig: ResMut<CameraRig>,
+) {
+    let target = zoom_level.target.clamp(zoom_level.min, zoom_level.max);
+    if rig.zoom == target {
+        return;
+    }
+
+    let blend = (-time.delta_seconds() / ZOOM_SMOOTH_TIME).exp();
+    let zoom = (target.ln() + (rig.zoom.ln() - target.ln()) * blend).exp();
+    rig.zoom = if (zoom - target).abs() < 0.0001 {
+        target
+    } else {
+        zoom
+    };
+}
+
+pub fn handle_camera_shake(
+    mut trauma: ResMut<CameraTrauma>,
+    mut read_camera_shake: EventReader<CameraShake>,
+) {
+    for shake in read_camera_shake.read() {
+        trauma.trauma = (trauma.trauma + shake.trauma).clamp(0.0, 1.0);
+    }
+}
+
+// virtual time, so a shake freezes with the game when it is paused
+pub fn update_camera_shake(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut trauma: ResMut<CameraTrauma>,
+    mut rig: ResMut<CameraRig>,
+) {
+    if trauma.trauma <= 0.0 {
+        if rig.shake_offset != Vec2::ZERO || rig.shake_roll != 0.0 {
+            rig.shake_offset = Vec2::ZERO;
+            rig.shake_roll = 0.0;
+        }
+        return;
+    }
+
+    trauma.elapsed += time.delta_seconds();
+    let shake = trauma.trauma.powi(2) * settings.accessibility.screen_shake.clamp(0.0, 1.0);
+    let t = trauma.elapsed * SHAKE_FREQUENCY;
+    rig.shake_offset =
+        SHAKE_MAX_OFFSET * shake * Vec2::new(shake_noise(1.0, t), shake_noise(2.0, t));
+    rig.shake_roll = SHAKE_MAX_ROLL * shake * shake_noise(3.0, t);
+    trauma.trauma = (trauma.trauma - TRAUMA_DECAY * time.delta_seconds()).max(0.0);
+}
 
-            new_scale = camera_2d.scale;
+// tests -----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDE: UVec2 = UVec2::new(2560, 1080);
+    const NARROW: UVec2 = UVec2::new(1200, 1600);
+    const SMALL: UVec2 = UVec2::new(200, 100);
+    const POLICIES: [CameraFitPolicy; 4] = [
+        CameraFitPolicy::Expand,
+        CameraFitPolicy::Letterbox,
+        CameraFitPolicy::Pillarbox,
+        CameraFitPolicy::IntegerScale,
+    ];
+
+    fn assert_near(a: f32, b: f32, epsilon: f32) {
+        assert!(
+            (a - b).abs() <= epsilon,
+            "{} is not within {} of {}",
+            a,
+            epsilon,
+            b
+        );
+    }
+
+    fn rig(
+        fit: CameraFit,
+        position: Vec2,
+        zoom: f32,
+        pixel_perfect: PixelPerfectMode,
+    ) -> CameraRig {
+        CameraRig {
+            position,
+            zoom,
+            fit,
+            pixel_perfect,
+            ..default()
+        }
+    }
 
-            if let ScalingMode::Fixed { height, .. } = camera_2d.scaling_mode {
-                aspect_ratio_y = height;
+    // holds for every policy and window: centred inside the window, unstretched, safe area shown
+    #[test]
+    fn fit_contains_the_safe_area_in_a_centred_viewport() {
+        for window_size in [WIDE, NARROW, SMALL] {
+            for policy in POLICIES {
+                let fit = CameraFit::new(window_size, policy);
+                let context = format!("{:?} in {:?}", policy, window_size);
+
+                assert!(
+                    fit.viewport_size.cmple(window_size).all(),
+                    "viewport overflows, {}",
+                    context
+                );
+                let centred = fit.viewport_position * 2 + fit.viewport_size;
+                assert!(
+                    centred.x.abs_diff(window_size.x) <= 1
+                        && centred.y.abs_diff(window_size.y) <= 1,
+                    "viewport is not centred, {}",
+                    context
+                );
+                assert!(
+                    fit.ortho_size.cmpge(SAFE_AREA).all(),
+                    "safe area is cut off, {}",
+                    context
+                );
+
+                let viewport = fit.viewport_size.as_vec2();
+                assert_near(
+                    fit.ortho_size.x / fit.ortho_size.y,
+                    viewport.x / viewport.y,
+                    0.01,
+                );
             }
         }
+    }
 
-        // top = y coordinate of perspective projection frustum top as it intersects z=0 plane (stage)
-        let new_top = aspect_ratio_y * 0.5 * new_scale;
+    #[test]
+    fn expand_fills_the_window() {
+        for window_size in [WIDE, NARROW, SMALL] {
+            let fit = CameraFit::new(window_size, CameraFitPolicy::Expand);
 
-        for (projection_3d, transform_3d) in perspective_query.iter_mut() {
-            let camera_z_pos = &transform_3d.translation.z;
-            let new_fov = 2.0 * (new_top / camera_z_pos).atan();
+            assert_eq!(fit.viewport_size, window_size);
+            assert_eq!(fit.viewport_position, UVec2::ZERO);
+            assert!(fit.viewport().is_none());
+        }
+    }
+
+    #[test]
+    fn letterbox_adds_bars_on_narrow_windows_only() {
+        let fit = CameraFit::new(NARROW, CameraFitPolicy::Letterbox);
+        assert_eq!(fit.ortho_size, SAFE_AREA);
+        assert_eq!(fit.viewport_size, UVec2::new(1200, 720));
+        assert_eq!(fit.viewport_position, UVec2::new(0, 440));
 
-            if let Perspective(proj) = projection_3d.into_inner() {
-                proj.fov = new_fov;
-                // proj.fov is actually clamped by 2d camera scale, because height used to calculate fov
-                // proj.fov = proj.fov.clamp(10.0f32.to_radians(), 130.0f32.to_radians());
+        let fit = CameraFit::new(WIDE, CameraFitPolicy::Letterbox);
+        assert_eq!(fit.viewport_size, WIDE);
+        assert_eq!(
+            fit.ortho_size,
+            CameraFit::new(WIDE, CameraFitPolicy::Expand).ortho_size
+        );
+    }
+
+    #[test]
+    fn pillarbox_adds_bars_on_wide_windows_only() {
+        let fit = CameraFit::new(WIDE, CameraFitPolicy::Pillarbox);
+        assert_eq!(fit.ortho_size, SAFE_AREA);
+        assert_eq!(fit.viewport_size, UVec2::new(1800, 1080));
+        assert_eq!(fit.viewport_position, UVec2::new(380, 0));
+
+        let fit = CameraFit::new(NARROW, CameraFitPolicy::Pillarbox);
+        assert_eq!(fit.viewport_size, NARROW);
+        assert_eq!(
+            fit.ortho_size,
+            CameraFit::new(NARROW, CameraFitPolicy::Expand).ortho_size
+        );
+    }
+
+    #[test]
+    fn integer_scale_uses_whole_multiples_when_the_window_is_large_enough() {
+        let fit = CameraFit::new(WIDE, CameraFitPolicy::IntegerScale);
+        assert_eq!(fit.ortho_size, SAFE_AREA);
+        assert_eq!(fit.viewport_size, UVec2::new(1680, 1008)); // 7x
+        assert_eq!(fit.viewport_position, UVec2::new(440, 36));
+
+        let fit = CameraFit::new(NARROW, CameraFitPolicy::IntegerScale);
+        assert_eq!(fit.viewport_size, UVec2::new(1200, 720)); // 5x
+        assert_eq!(fit.viewport_position, UVec2::new(0, 440));
+    }
+
+    #[test]
+    fn integer_scale_fits_windows_smaller_than_the_safe_area() {
+        let fit = CameraFit::new(SMALL, CameraFitPolicy::IntegerScale);
+        assert_eq!(fit.ortho_size, SAFE_AREA);
+        assert_eq!(fit.viewport_size, UVec2::new(167, 100));
+        assert_eq!(fit.viewport_position, UVec2::new(16, 0));
+    }
+
+    // the perspective World camera shows the same area of the z=0 plane as the Stage camera
+    #[test]
+    fn world_fov_shows_the_visible_size_at_the_stage_plane() {
+        for policy in POLICIES {
+            for zoom in [0.25, 0.5, 1.0, 2.0, 3.7] {
+                let rig = rig(
+                    CameraFit::new(WIDE, policy),
+                    Vec2::ZERO,
+                    zoom,
+                    PixelPerfectMode::Off,
+                );
+
+                let shown_height = 2.0 * rig.fit.camera_z * (rig.world_fov() * 0.5).tan();
+                assert_near(shown_height, rig.visible_size().y, 0.01);
             }
         }
     }
+
+    #[test]
+    fn clamp_keeps_the_view_inside_the_bounds() {
+        let bounds = Rect::new(0.0, 0.0, 1000.0, 500.0);
+        let visible_size = Vec2::new(200.0, 100.0);
+
+        assert_eq!(
+            clamp_to_bounds(Vec2::new(-50.0, 480.0), visible_size, bounds),
+            Vec2::new(100.0, 450.0)
+        );
+        assert_eq!(
+            clamp_to_bounds(Vec2::new(500.0, 250.0), visible_size, bounds),
+            Vec2::new(500.0, 250.0)
+        );
+    }
+
+    #[test]
+    fn clamp_centres_a_view_larger_than_the_bounds() {
+        let bounds = Rect::new(0.0, 0.0, 100.0, 50.0);
+
+        assert_eq!(
+            clamp_to_bounds(Vec2::new(-30.0, 80.0), Vec2::new(300.0, 200.0), bounds),
+            Vec2::new(50.0, 25.0)
+        );
+        // only the axis that does not fit is centred
+        assert_eq!(
+            clamp_to_bounds(Vec2::new(90.0, 80.0), Vec2::new(50.0, 200.0), bounds),
+            Vec2::new(75.0, 25.0)
+        );
+    }
+
+    #[test]
+    fn snapped_moves_the_view_in_whole_stage_pixels() {
+        let fit = CameraFit::new(UVec2::new(2560, 1440), CameraFitPolicy::IntegerScale);
+
+        for zoom in [1.0, 2.0] {
+            let rig = rig(
+                fit,
+                Vec2::new(10.26, -3.74),
+                zoom,
+                PixelPerfectMode::Snapped,
+            );
+            let (snapped, offset) = rig.pixel_snap();
+
+            assert_eq!(offset, Vec2::ZERO);
+            assert_eq!(snapped / zoom, (snapped / zoom).round());
+            assert_eq!(rig.shown_position(), snapped);
+            assert!((rig.view_position() - snapped)
+                .abs()
+                .cmple(Vec2::splat(zoom * 0.5))
+                .all());
+        }
+    }
+
+    #[test]
+    fn smoothed_offsets_the_canvas_in_whole_screen_pixels() {
+        // 10 screen pixels per stage pixel
+        let fit = CameraFit::new(UVec2::new(2560, 1440), CameraFitPolicy::IntegerScale);
+        let scale = 10.0;
+
+        for zoom in [1.0, 2.0] {
+            let rig = rig(
+                fit,
+                Vec2::new(10.26, -3.74),
+                zoom,
+                PixelPerfectMode::Smoothed,
+            );
+            let (snapped, offset) = rig.pixel_snap();
+
+            assert_eq!(snapped / zoom, (snapped / zoom).round());
+            let screen_offset = offset * scale;
+            assert_near(screen_offset.x, screen_offset.x.round(), 0.001);
+            assert_near(screen_offset.y, screen_offset.y.round(), 0.001);
+
+            // within half a screen pixel of where the view actually is
+            let error = (rig.shown_position() - rig.view_position()).abs();
+            assert!(error.cmple(Vec2::splat(zoom * 0.5 / scale + 0.001)).all());
+        }
+    }
 }
@@ -1,9 +1,12 @@
 use super::{
-    materials::{materials_transition::*, materials_ui::*},
+    materials::{materials_game::*, materials_transition::*, materials_ui::*},
+    post_process::PostProcessPlugin,
     transition::*,
+    transition_sequence::*,
 };
+use crate::states::{app_state, options_state};
 
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Material2dPlugin};
 
 // plugins ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -16,8 +19,46 @@ impl Plugin for GraphicsManagerPlugin {
             UiMaterialPlugin::<FocusedButtonMaterial>::default(),
             UiMaterialPlugin::<FadeToColorMaterial>::default(),
             UiMaterialPlugin::<FadeFromColorMaterial>::default(),
+            UiMaterialPlugin::<ShapeTransitionMaterial>::default(),
+            PostProcessPlugin,
         ))
+        .add_plugins((
+            Material2dPlugin::<SpriteOutlineMaterial>::default(),
+            Material2dPlugin::<HitFlashMaterial>::default(),
+            Material2dPlugin::<DissolveMaterial>::default(),
+            Material2dPlugin::<PaletteSwapMaterial>::default(),
+            Material2dPlugin::<SilhouetteMaterial>::default(),
+        ))
+        .init_resource::<GameMaterialTime>()
+        .init_resource::<TransitionSequencer>()
         .add_event::<Transition>()
-        .add_systems(Update, (spawn_transition, animate_transition));
+        .add_event::<SequenceTransition>()
+        .add_event::<TransitionFinished>()
+        .add_systems(
+            Update,
+            (
+                spawn_transition,
+                animate_transition,
+                update_iris_transitions,
+                // the state edits are handled in the frame they are sent
+                (start_transition_sequence, advance_transition_sequence)
+                    .chain()
+                    .before(app_state::handle_edit_app_state)
+                    .before(options_state::handle_edit_options_state),
+                log_transition_finished,
+                (
+                    update_game_material_time,
+                    (
+                        apply_game_material_time::<SpriteOutlineMaterial>,
+                        apply_game_material_time::<HitFlashMaterial>,
+                        apply_game_material_time::<DissolveMaterial>,
+                        apply_game_material_time::<PaletteSwapMaterial>,
+                        apply_game_material_time::<SilhouetteMaterial>,
+                    )
+                        .run_if(resource_changed::<GameMaterialTime>),
+                )
+                    .chain(),
+            ),
+        );
     }
 }
@@ -1 +1,257 @@
+// a library for gameplay code, the constructors and setters are allowed to be unused until
+//  gameplay spawns the materials
+use bevy::{
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::Material2d,
+};
 
+// Game Material TIME ----------------------------------------------------------
+// -----------------------------------------------------------------------------
+// shared by every stage material, virtual time so they freeze with the game when it is paused
+#[derive(Resource, Debug, Default)]
+pub struct GameMaterialTime {
+    pub seconds: f32, // wraps every hour, so shaders keep their precision
+}
+
+// stage materials take their time uniform from GameMaterialTime
+pub trait GameMaterial: Material2d {
+    fn set_time(&mut self, seconds: f32);
+}
+
+pub fn update_game_material_time(time: Res<Time>, mut material_time: ResMut<GameMaterialTime>) {
+    if time.delta_seconds() > 0.0 {
+        material_time.seconds = time.elapsed_seconds_wrapped();
+    }
+}
+
+pub fn apply_game_material_time<M: GameMaterial>(
+    material_time: Res<GameMaterialTime>,
+    mut game_materials: ResMut<Assets<M>>,
+) {
+    for (_, material) in game_materials.iter_mut() {
+        material.set_time(material_time.seconds);
+    }
+}
+
+// Game SPRITE OUTLINE ---------------------------------------------------------
+// -----------------------------------------------------------------------------
+// the outline is drawn outside the sprite, its texture needs transparent texels around the art
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct SpriteOutlineMaterial {
+    #[uniform(0)]
+    pub time: f32,
+    #[uniform(1)]
+    pub color: Vec4,
+    #[uniform(2)]
+    pub params: Vec4, // x = thickness in texels, y = pulses per second
+    #[texture(3)]
+    #[sampler(4)]
+    pub color_texture: Option<Handle<Image>>,
+}
+
+#[allow(dead_code)]
+impl SpriteOutlineMaterial {
+    pub fn new(color_texture: Handle<Image>, color: Vec4, thickness: f32) -> Self {
+        SpriteOutlineMaterial {
+            time: 0.0,
+            color,
+            params: Vec4::new(thickness, 0.0, 0.0, 0.0),
+            color_texture: Some(color_texture),
+        }
+    }
+}
+
+impl Material2d for SpriteOutlineMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/fragment/game_outline.wgsl".into()
+    }
+}
+
+impl GameMaterial for SpriteOutlineMaterial {
+    fn set_time(&mut self, seconds: f32) {
+        self.time = seconds;
+    }
+}
+
+// Game HIT FLASH --------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// gameplay sets the flash amount (params.x) when hit and eases it back to 0.0
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct HitFlashMaterial {
+    #[uniform(0)]
+    pub time: f32,
+    #[uniform(1)]
+    pub color: Vec4,
+    #[uniform(2)]
+    pub params: Vec4, // x = flash amount, y = flickers per second
+    #[texture(3)]
+    #[sampler(4)]
+    pub color_texture: Option<Handle<Image>>,
+}
+
+#[allow(dead_code)]
+impl HitFlashMaterial {
+    pub fn new(color_texture: Handle<Image>, color: Vec4) -> Self {
+        HitFlashMaterial {
+            time: 0.0,
+            color,
+            params: Vec4::ZERO,
+            color_texture: Some(color_texture),
+        }
+    }
+
+    pub fn set_flash(&mut self, amount: f32) {
+        self.params.x = amount.clamp(0.0, 1.0);
+    }
+}
+
+impl Material2d for HitFlashMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/fragment/game_hit_flash.wgsl".into()
+    }
+}
+
+impl GameMaterial for HitFlashMaterial {
+    fn set_time(&mut self, seconds: f32) {
+        self.time = seconds;
+    }
+}
+
+// Game DISSOLVE ---------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// noise below the threshold is cut away, the color glows along the edge of the cut
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct DissolveMaterial {
+    #[uniform(0)]
+    pub time: f32,
+    #[uniform(1)]
+    pub color: Vec4,
+    #[uniform(2)]
+    pub params: Vec4, // x = threshold (0.0 whole - 1.0 gone), y = edge width, z = noise cells
+    #[texture(3)]
+    #[sampler(4)]
+    pub color_texture: Option<Handle<Image>>,
+}
+
+#[allow(dead_code)]
+impl DissolveMaterial {
+    pub fn new(color_texture: Handle<Image>, color: Vec4) -> Self {
+        DissolveMaterial {
+            time: 0.0,
+            color,
+            params: Vec4::new(0.0, 0.08, 8.0, 0.0),
+            color_texture: Some(color_texture),
+        }
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.params.x = threshold.clamp(0.0, 1.0);
+    }
+}
+
+impl Material2d for DissolveMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/fragment/game_dissolve.wgsl".into()
+    }
+}
+
+impl GameMaterial for DissolveMaterial {
+    fn set_time(&mut self, seconds: f32) {
+        self.time = seconds;
+    }
+}
+
+// Game PALETTE SWAP -----------------------------------------------------------
+// -----------------------------------------------------------------------------
+// the red channel of the sprite is a palette index, so its texture has to be loaded as linear
+//  the palette has one palette per row, params.x picks the row
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct PaletteSwapMaterial {
+    #[uniform(0)]
+    pub time: f32,
+    #[uniform(1)]
+    pub params: Vec4, // x = palette row, y = first cycled index, z = cycled count, w = steps per second
+    #[texture(2)]
+    #[sampler(3)]
+    pub color_texture: Option<Handle<Image>>,
+    #[texture(4)]
+    pub palette_texture: Option<Handle<Image>>,
+}
+
+#[allow(dead_code)]
+impl PaletteSwapMaterial {
+    pub fn new(color_texture: Handle<Image>, palette_texture: Handle<Image>, row: u32) -> Self {
+        PaletteSwapMaterial {
+            time: 0.0,
+            params: Vec4::new(row as f32, 0.0, 0.0, 0.0),
+            color_texture: Some(color_texture),
+            palette_texture: Some(palette_texture),
+        }
+    }
+
+    // indices first..first + count rotate by steps_per_second, 0 count stops the cycle
+    pub fn with_cycle(mut self, first: u32, count: u32, steps_per_second: f32) -> Self {
+        self.params.y = first as f32;
+        self.params.z = count as f32;
+        self.params.w = steps_per_second;
+        self
+    }
+}
+
+impl Material2d for PaletteSwapMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/fragment/game_palette_swap.wgsl".into()
+    }
+}
+
+impl GameMaterial for PaletteSwapMaterial {
+    fn set_time(&mut self, seconds: f32) {
+        self.time = seconds;
+    }
+}
+
+// Game SILHOUETTE -------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// a copy of the sprite drawn in front of the occluders, faded in while its owner is behind one
+//  2D has no depth buffer, so gameplay decides when the owner is occluded (params.x)
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct SilhouetteMaterial {
+    #[uniform(0)]
+    pub time: f32,
+    #[uniform(1)]
+    pub color: Vec4,
+    #[uniform(2)]
+    pub params: Vec4, // x = occluded (0.0 hidden - 1.0 shown), y = pulses per second
+    #[texture(3)]
+    #[sampler(4)]
+    pub color_texture: Option<Handle<Image>>,
+}
+
+#[allow(dead_code)]
+impl SilhouetteMaterial {
+    pub fn new(color_texture: Handle<Image>, color: Vec4) -> Self {
+        SilhouetteMaterial {
+            time: 0.0,
+            color,
+            params: Vec4::ZERO,
+            color_texture: Some(color_texture),
+        }
+    }
+
+    pub fn set_occluded(&mut self, occluded: f32) {
+        self.params.x = occluded.clamp(0.0, 1.0);
+    }
+}
+
+impl Material2d for SilhouetteMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/fragment/game_silhouette.wgsl".into()
+    }
+}
+
+impl GameMaterial for SilhouetteMaterial {
+    fn set_time(&mut self, seconds: f32) {
+        self.time = seconds;
+    }
+}
@@ -34,3 +34,24 @@ impl UiMaterial for FadeFromColorMaterial {
         "shaders/fragment/transition_fade_color.wgsl".into()
     }
 }
+
+// Transition SHAPE ------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// wipe, iris, tile and noise transitions, see TransitionOption for the meaning of each field
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct ShapeTransitionMaterial {
+    #[uniform(0)]
+    pub time: f32,
+    #[uniform(1)]
+    pub color: Vec4,
+    #[uniform(2)]
+    pub settings: UVec4,
+    #[uniform(3)]
+    pub params: Vec4,
+}
+
+impl UiMaterial for ShapeTransitionMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/fragment/transition_shape.wgsl".into()
+    }
+}
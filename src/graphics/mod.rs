@@ -2,3 +2,4 @@ pub mod graphics_manager;
 pub mod materials;
 pub mod post_process;
 pub mod transition;
+pub mod transition_sequence;
@@ -1 +1,505 @@
+/* -----------------------------------------------------------------------------
+    Full screen effects on the UI camera, so they cover the World, Stage and UI together
+        the cameras composite into one shared main texture, see game/cameras.rs
+            World clears it, Stage (or Upscale when pixel perfect) and UI draw over it
+        PostProcessNode runs after the UI pass and before the UI camera writes to the window
+    PostProcessSettings on a camera lists the effects in the order they run
+        each effect is one full screen pass reading the output of the one before it
+        effects can be toggled and tuned at runtime, in code or in the inspector
+        a camera with every effect disabled skips the node entirely
+    All effects share one shader (shaders/fragment/post_process.wgsl)
+        an entry point per effect, one uniform with every effect's parameters
+----------------------------------------------------------------------------- */
+use crate::game::cameras::SAFE_AREA;
 
+use bevy::{
+    core_pipeline::{
+        core_2d::graph::{Core2d, Node2d},
+        fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    },
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        globals::{GlobalsBuffer, GlobalsUniform},
+        render_graph::{
+            NodeRunError, RenderGraphApp, RenderGraphContext, RenderLabel, ViewNode, ViewNodeRunner,
+        },
+        render_resource::{
+            binding_types::{sampler, texture_2d, uniform_buffer},
+            BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, CachedRenderPipelineId,
+            ColorTargetState, ColorWrites, FragmentState, MultisampleState, Operations,
+            PipelineCache, PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor,
+            RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderStages,
+            SpecializedRenderPipeline, SpecializedRenderPipelines, TextureFormat,
+            TextureSampleType,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::BevyDefault,
+        view::{ExtractedView, ViewTarget},
+        Render, RenderApp, RenderSet,
+    },
+    ui::graph::NodeUi,
+};
+
+pub const POST_PROCESS_SHADER: &str = "shaders/fragment/post_process.wgsl";
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct PostProcessPlugin;
+
+impl Plugin for PostProcessPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PostProcessSettings>().add_plugins((
+            ExtractComponentPlugin::<PostProcessSettings>::default(),
+            UniformComponentPlugin::<PostProcessUniform>::default(),
+        ));
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<SpecializedRenderPipelines<PostProcessPipeline>>()
+            .add_systems(
+                Render,
+                prepare_post_process_pipelines.in_set(RenderSet::Prepare),
+            )
+            .add_render_graph_node::<ViewNodeRunner<PostProcessNode>>(Core2d, PostProcessLabel)
+            .add_render_graph_edges(
+                Core2d,
+                (NodeUi::UiPass, PostProcessLabel, Node2d::Upscaling),
+            );
+    }
+
+    // the pipeline needs the render device, which only exists once the renderer is set up
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<PostProcessPipeline>();
+    }
+}
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PostProcessEffect {
+    Vignette,
+    ChromaticAberration,
+    Crt,
+    FilmGrain,
+    ColorGrading,
+}
+
+impl PostProcessEffect {
+    // the fragment entry point in the post process shader
+    pub fn entry_point(&self) -> &'static str {
+        match self {
+            PostProcessEffect::Vignette => "vignette",
+            PostProcessEffect::ChromaticAberration => "chromatic_aberration",
+            PostProcessEffect::Crt => "crt",
+            PostProcessEffect::FilmGrain => "film_grain",
+            PostProcessEffect::ColorGrading => "color_grading",
+        }
+    }
+}
+
+// darkens (or tints) the screen towards its edges
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct VignetteEffect {
+    pub enabled: bool,
+    pub intensity: f32,
+    pub radius: f32, // 1.0 reaches the middle of the longer screen edge
+    pub softness: f32,
+    pub color: Color,
+}
+
+impl Default for VignetteEffect {
+    fn default() -> VignetteEffect {
+        VignetteEffect {
+            enabled: false,
+            intensity: 0.5,
+            radius: 1.2,
+            softness: 0.8,
+            color: Color::BLACK,
+        }
+    }
+}
+
+// splits red and blue away from green, more towards the edges
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct ChromaticAberrationEffect {
+    pub enabled: bool,
+    pub intensity: f32, // pixels at the corners
+}
+
+impl Default for ChromaticAberrationEffect {
+    fn default() -> ChromaticAberrationEffect {
+        ChromaticAberrationEffect {
+            enabled: false,
+            intensity: 3.0,
+        }
+    }
+}
+
+// curved screen with dark scanlines
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct CrtEffect {
+    pub enabled: bool,
+    pub curvature: f32, // 0.0 is flat
+    pub scanline_intensity: f32,
+    pub scanline_count: f32, // over the whole screen height
+}
+
+impl Default for CrtEffect {
+    fn default() -> CrtEffect {
+        CrtEffect {
+            enabled: false,
+            curvature: 0.05,
+            scanline_intensity: 0.2,
+            scanline_count: SAFE_AREA.y, // one per stage pixel row
+        }
+    }
+}
+
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct FilmGrainEffect {
+    pub enabled: bool,
+    pub intensity: f32,
+    pub size: f32, // pixels
+}
+
+impl Default for FilmGrainEffect {
+    fn default() -> FilmGrainEffect {
+        FilmGrainEffect {
+            enabled: false,
+            intensity: 0.08,
+            size: 2.0,
+        }
+    }
+}
+
+// applied to the tonemapped image, in that order
+#[derive(Reflect, Debug, Clone, Copy)]
+pub struct ColorGradingEffect {
+    pub enabled: bool,
+    pub exposure: f32, // stops
+    pub contrast: f32, // around middle grey
+    pub saturation: f32,
+    pub tint: Color,
+}
+
+impl Default for ColorGradingEffect {
+    fn default() -> ColorGradingEffect {
+        ColorGradingEffect {
+            enabled: false,
+            exposure: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            tint: Color::WHITE,
+        }
+    }
+}
+
+// the pipeline shared by every effect, specialized per effect and view format
+#[derive(Resource)]
+pub struct PostProcessPipeline {
+    pub layout: BindGroupLayout,
+    pub sampler: Sampler,
+    pub shader: Handle<Shader>,
+}
+
+impl FromWorld for PostProcessPipeline {
+    fn from_world(world: &mut World) -> PostProcessPipeline {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "post_process_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<PostProcessUniform>(true),
+                    uniform_buffer::<GlobalsUniform>(false),
+                ),
+            ),
+        );
+        let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+        let shader = world.resource::<AssetServer>().load(POST_PROCESS_SHADER);
+
+        PostProcessPipeline {
+            layout,
+            sampler,
+            shader,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct PostProcessPipelineKey {
+    pub effect: PostProcessEffect,
+    pub hdr: bool,
+}
+
+impl SpecializedRenderPipeline for PostProcessPipeline {
+    type Key = PostProcessPipelineKey;
+
+    fn specialize(&self, key: PostProcessPipelineKey) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some(format!("post_process_{}", key.effect.entry_point()).into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: vec![],
+                entry_point: key.effect.entry_point().into(),
+                targets: vec![Some(ColorTargetState {
+                    format: if key.hdr {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        TextureFormat::bevy_default()
+                    },
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: vec![],
+        }
+    }
+}
+
+// components ------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct PostProcessSettings {
+    pub order: Vec<PostProcessEffect>, // effects missing from the order never run
+    pub vignette: VignetteEffect,
+    pub chromatic_aberration: ChromaticAberrationEffect,
+    pub crt: CrtEffect,
+    pub film_grain: FilmGrainEffect,
+    pub color_grading: ColorGradingEffect,
+}
+
+impl Default for PostProcessSettings {
+    fn default() -> PostProcessSettings {
+        PostProcessSettings {
+            order: vec![
+                PostProcessEffect::ColorGrading,
+                PostProcessEffect::ChromaticAberration,
+                PostProcessEffect::Crt,
+                PostProcessEffect::Vignette,
+                PostProcessEffect::FilmGrain,
+            ],
+            vignette: VignetteEffect::default(),
+            chromatic_aberration: ChromaticAberrationEffect::default(),
+            crt: CrtEffect::default(),
+            film_grain: FilmGrainEffect::default(),
+            color_grading: ColorGradingEffect::default(),
+        }
+    }
+}
+
+impl PostProcessSettings {
+    pub fn is_enabled(&self, effect: PostProcessEffect) -> bool {
+        match effect {
+            PostProcessEffect::Vignette => self.vignette.enabled,
+            PostProcessEffect::ChromaticAberration => self.chromatic_aberration.enabled,
+            PostProcessEffect::Crt => self.crt.enabled,
+            PostProcessEffect::FilmGrain => self.film_grain.enabled,
+            PostProcessEffect::ColorGrading => self.color_grading.enabled,
+        }
+    }
+
+    pub fn uniform(&self) -> PostProcessUniform {
+        PostProcessUniform {
+            vignette_color: Vec4::from(self.vignette.color.as_linear_rgba_f32()),
+            vignette_intensity: self.vignette.intensity,
+            vignette_radius: self.vignette.radius,
+            vignette_softness: self.vignette.softness,
+            aberration_intensity: self.chromatic_aberration.intensity,
+            crt_curvature: self.crt.curvature,
+            scanline_intensity: self.crt.scanline_intensity,
+            scanline_count: self.crt.scanline_count,
+            grain_intensity: self.film_grain.intensity,
+            grain_size: self.film_grain.size,
+            exposure: self.color_grading.exposure,
+            contrast: self.color_grading.contrast,
+            saturation: self.color_grading.saturation,
+            tint: Vec4::from(self.color_grading.tint.as_linear_rgba_f32()),
+        }
+    }
+}
+
+pub use uniform::PostProcessUniform;
+
+// the ShaderType derive generates layout checks that are never called in a binary crate,
+//  an item level allow does not reach them so the uniform gets a module of its own
+mod uniform {
+    #![allow(dead_code)]
+
+    use bevy::{prelude::*, render::render_resource::ShaderType};
+
+    // render world only, the parameters of every effect, see PostProcessUniform in the shader
+    #[derive(Component, ShaderType, Clone, Copy)]
+    pub struct PostProcessUniform {
+        pub vignette_color: Vec4,
+        pub vignette_intensity: f32,
+        pub vignette_radius: f32,
+        pub vignette_softness: f32,
+        pub aberration_intensity: f32,
+        pub crt_curvature: f32,
+        pub scanline_intensity: f32,
+        pub scanline_count: f32,
+        pub grain_intensity: f32,
+        pub grain_size: f32,
+        pub exposure: f32,
+        pub contrast: f32,
+        pub saturation: f32,
+        pub tint: Vec4,
+    }
+}
+
+// render world only, the enabled effects in the order they run
+#[derive(Component, Clone)]
+pub struct PostProcessChain {
+    pub effects: Vec<PostProcessEffect>,
+}
+
+// render world only, one pipeline per effect in the chain
+#[derive(Component)]
+pub struct ViewPostProcessPipelines {
+    pub pipeline_ids: Vec<CachedRenderPipelineId>,
+}
+
+// views without a chain are left alone, so disabling everything costs nothing
+impl ExtractComponent for PostProcessSettings {
+    type QueryData = &'static PostProcessSettings;
+    type QueryFilter = ();
+    type Out = (PostProcessUniform, PostProcessChain);
+
+    fn extract_component(settings: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        let effects: Vec<PostProcessEffect> = settings
+            .order
+            .iter()
+            .copied()
+            .filter(|effect| settings.is_enabled(*effect))
+            .collect();
+        if effects.is_empty() {
+            return None;
+        }
+
+        Some((settings.uniform(), PostProcessChain { effects }))
+    }
+}
+
+// render graph ----------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct PostProcessLabel;
+
+#[derive(Default)]
+pub struct PostProcessNode;
+
+impl ViewNode for PostProcessNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static ViewPostProcessPipelines,
+        &'static DynamicUniformIndex<PostProcessUniform>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, pipelines, uniform_index): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let post_process_pipeline = world.resource::<PostProcessPipeline>();
+        let Some(uniforms) = world
+            .resource::<ComponentUniforms<PostProcessUniform>>()
+            .uniforms()
+            .binding()
+        else {
+            return Ok(());
+        };
+        let Some(globals) = world.resource::<GlobalsBuffer>().buffer.binding() else {
+            return Ok(());
+        };
+
+        for pipeline_id in pipelines.pipeline_ids.iter() {
+            // effects whose shader is still compiling are skipped
+            let Some(pipeline) = pipeline_cache.get_render_pipeline(*pipeline_id) else {
+                continue;
+            };
+
+            // swaps the main textures, so each pass reads what the last one wrote
+            let post_process = view_target.post_process_write();
+            let bind_group = render_context.render_device().create_bind_group(
+                "post_process_bind_group",
+                &post_process_pipeline.layout,
+                &BindGroupEntries::sequential((
+                    post_process.source,
+                    &post_process_pipeline.sampler,
+                    uniforms.clone(),
+                    globals.clone(),
+                )),
+            );
+
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("post_process_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: post_process.destination,
+                    resolve_target: None,
+                    ops: Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_render_pipeline(pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn prepare_post_process_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<PostProcessPipeline>>,
+    post_process_pipeline: Res<PostProcessPipeline>,
+    view_query: Query<(Entity, &ExtractedView, &PostProcessChain)>,
+) {
+    for (entity, view, chain) in view_query.iter() {
+        let pipeline_ids = chain
+            .effects
+            .iter()
+            .map(|effect| {
+                pipelines.specialize(
+                    &pipeline_cache,
+                    &post_process_pipeline,
+                    PostProcessPipelineKey {
+                        effect: *effect,
+                        hdr: view.hdr,
+                    },
+                )
+            })
+            .collect();
+
+        commands
+            .entity(entity)
+            .insert(ViewPostProcessPipelines { pipeline_ids });
+    }
+}
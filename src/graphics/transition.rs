@@ -1,14 +1,137 @@
 #![allow(unused)]
 
-use crate::graphics::materials::materials_transition::*;
+use crate::{game::cameras::WorldCamera, graphics::materials::materials_transition::*};
 
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
+
+// transitions draw above every menu, including ones spawned while the screen is covered
+//  a transition's layer is added on top, so concurrent transitions stack in a known order
+pub const TRANSITION_Z_INDEX: i32 = 1_000_000;
+pub const FADE_SECONDS: f32 = 1.0;
+pub const TILE_SIZE: f32 = 48.0;
 
 // resources -------------------------------------------------------------------
 // -----------------------------------------------------------------------------
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TransitionOption {
     FadeToColor(Vec4),
     FadeFromColor(Vec4),
+    // the side the wipe moves towards
+    Wipe(ShapeTransition, WipeDirection),
+    // circle centred on a point in the game world, it follows the point if the camera moves
+    Iris(ShapeTransition, Vec3),
+    TileDissolve(ShapeTransition, TileShape),
+    NoiseDissolve(ShapeTransition),
+}
+
+impl TransitionOption {
+    pub fn duration(&self) -> f32 {
+        match self {
+            TransitionOption::FadeToColor(_) | TransitionOption::FadeFromColor(_) => FADE_SECONDS,
+            TransitionOption::Wipe(shape, _)
+            | TransitionOption::Iris(shape, _)
+            | TransitionOption::TileDissolve(shape, _)
+            | TransitionOption::NoiseDissolve(shape) => shape.duration,
+        }
+    }
+}
+
+// cover goes from a clear screen to a solid color, reveal goes the other way
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShapeTransition {
+    pub color: Vec4,
+    pub is_reveal: bool,
+    pub duration: f32,
+    pub easing: TransitionEasing,
+}
+
+impl ShapeTransition {
+    pub fn cover(color: Vec4) -> Self {
+        ShapeTransition {
+            color,
+            is_reveal: false,
+            duration: 1.0,
+            easing: TransitionEasing::Linear,
+        }
+    }
+
+    pub fn reveal(color: Vec4) -> Self {
+        ShapeTransition {
+            is_reveal: true,
+            ..ShapeTransition::cover(color)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl WipeDirection {
+    // in ui coordinates, y points down
+    pub fn vector(&self) -> Vec2 {
+        match self {
+            WipeDirection::Left => Vec2::new(-1.0, 0.0),
+            WipeDirection::Right => Vec2::new(1.0, 0.0),
+            WipeDirection::Up => Vec2::new(0.0, -1.0),
+            WipeDirection::Down => Vec2::new(0.0, 1.0),
+        }
+    }
+}
+
+// the index is passed to the shader, keep the order in sync with ease() in transition_shape.wgsl
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TransitionEasing {
+    #[default]
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuarticIn,
+    QuarticOut,
+    QuarticInOut,
+    QuinticIn,
+    QuinticOut,
+    QuinticInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExponentialIn,
+    ExponentialOut,
+    ExponentialInOut,
+    CircularIn,
+    CircularOut,
+    CircularInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TileShape {
+    Diamond,
+    Hexagon,
+}
+
+// the assets every transition node takes its material from
+#[derive(SystemParam)]
+pub struct TransitionMaterials<'w> {
+    pub fade_to_color: ResMut<'w, Assets<FadeToColorMaterial>>,
+    pub fade_from_color: ResMut<'w, Assets<FadeFromColorMaterial>>,
+    pub shape_transition: ResMut<'w, Assets<ShapeTransitionMaterial>>,
 }
 
 // events ----------------------------------------------------------------------
@@ -16,6 +139,8 @@ pub enum TransitionOption {
 #[derive(Event)]
 pub struct Transition {
     pub desired_transition: TransitionOption,
+    // higher layers draw above lower ones
+    pub layer: i32,
 }
 
 // components ------------------------------------------------------------------
@@ -23,6 +148,151 @@ pub struct Transition {
 #[derive(Component)]
 pub struct TransitionNode {
     pub timer: Timer,
+    // a finished fade to color is kept covering the screen when false, its owner despawns it
+    pub despawn_on_finish: bool,
+}
+
+// the material handle a transition node owns, only one of them is set
+pub type TransitionMaterialHandles = AnyOf<(
+    &'static Handle<FadeToColorMaterial>,
+    &'static Handle<FadeFromColorMaterial>,
+    &'static Handle<ShapeTransitionMaterial>,
+)>;
+
+#[derive(Component)]
+pub struct IrisTarget {
+    pub world_position: Vec3,
+}
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// each node owns the only strong handle to its material, so despawning the node releases it
+pub fn spawn_transition_node(
+    commands: &mut Commands,
+    desired_transition: TransitionOption,
+    layer: i32,
+    despawn_on_finish: bool,
+    materials: &mut TransitionMaterials,
+) -> Entity {
+    let transition_node = (
+        Name::new("TransitionNode"),
+        TransitionNode {
+            timer: Timer::from_seconds(desired_transition.duration(), TimerMode::Once),
+            despawn_on_finish,
+        },
+    );
+    let z_index = ZIndex::Global(TRANSITION_Z_INDEX.saturating_add(layer));
+
+    match desired_transition {
+        TransitionOption::FadeToColor(desired_color) => {
+            let material = materials.fade_to_color.add(FadeToColorMaterial {
+                time: 0.0,
+                color: desired_color,
+            });
+            commands
+                .spawn((transition_node, transition_bundle(material, z_index)))
+                .id()
+        }
+        TransitionOption::FadeFromColor(desired_color) => {
+            let material = materials.fade_from_color.add(FadeFromColorMaterial {
+                time: 1.0,
+                color: desired_color,
+            });
+            commands
+                .spawn((transition_node, transition_bundle(material, z_index)))
+                .id()
+        }
+        TransitionOption::Wipe(shape, direction) => {
+            let material = materials.shape_transition.add(shape_material(
+                shape,
+                0,
+                0,
+                direction.vector().extend(0.0).extend(0.0),
+            ));
+            commands
+                .spawn((transition_node, transition_bundle(material, z_index)))
+                .id()
+        }
+        TransitionOption::Iris(shape, world_position) => {
+            // screen centre until the world point has been projected
+            let material = materials.shape_transition.add(shape_material(
+                shape,
+                1,
+                0,
+                Vec4::new(0.5, 0.5, 0.0, 0.0),
+            ));
+            commands
+                .spawn((
+                    transition_node,
+                    IrisTarget { world_position },
+                    transition_bundle(material, z_index),
+                ))
+                .id()
+        }
+        TransitionOption::TileDissolve(shape, tile_shape) => {
+            let tile_shape = match tile_shape {
+                TileShape::Diamond => 0,
+                TileShape::Hexagon => 1,
+            };
+            let material = materials.shape_transition.add(shape_material(
+                shape,
+                2,
+                tile_shape,
+                Vec4::new(0.0, 0.0, TILE_SIZE, 0.0),
+            ));
+            commands
+                .spawn((transition_node, transition_bundle(material, z_index)))
+                .id()
+        }
+        TransitionOption::NoiseDissolve(shape) => {
+            let material = materials
+                .shape_transition
+                .add(shape_material(shape, 3, 0, Vec4::ZERO));
+            commands
+                .spawn((transition_node, transition_bundle(material, z_index)))
+                .id()
+        }
+    }
+}
+
+pub fn transition_bundle<M: UiMaterial>(
+    material: Handle<M>,
+    z_index: ZIndex,
+) -> MaterialNodeBundle<M> {
+    MaterialNodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        z_index,
+        material,
+        ..default()
+    }
+}
+
+// shape_index and tile_shape pick the branches in transition_shape.wgsl
+pub fn shape_material(
+    shape: ShapeTransition,
+    shape_index: u32,
+    tile_shape: u32,
+    params: Vec4,
+) -> ShapeTransitionMaterial {
+    ShapeTransitionMaterial {
+        time: 0.0,
+        color: shape.color,
+        settings: UVec4::new(
+            shape_index,
+            shape.easing as u32,
+            shape.is_reveal as u32,
+            tile_shape,
+        ),
+        params,
+    }
 }
 
 // systems ---------------------------------------------------------------------
@@ -30,79 +300,88 @@ pub struct TransitionNode {
 pub fn spawn_transition(
     mut commands: Commands,
     mut read_transition_event: EventReader<Transition>,
-    mut fade_to_color_mat: ResMut<Assets<FadeToColorMaterial>>,
-    mut fade_from_color_mat: ResMut<Assets<FadeFromColorMaterial>>,
+    mut materials: TransitionMaterials,
 ) {
     for transition_event in read_transition_event.read() {
-        let transition = commands
-            .spawn((
-                Name::new("TransitionNode"),
-                TransitionNode {
-                    timer: Timer::from_seconds(1.0, TimerMode::Once),
-                },
-                MaterialNodeBundle {
-                    style: Style {
-                        width: Val::Percent(100.0),
-                        height: Val::Percent(100.0),
-                        align_items: AlignItems::Center,
-                        justify_content: JustifyContent::Center,
-                        flex_direction: FlexDirection::Column,
-                        ..default()
-                    },
-                    material: fade_to_color_mat.add(FadeToColorMaterial {
-                        time: 0.0,
-                        color: Vec4::new(0.0, 0.0, 0.0, 1.0), // default BLACK
-                    }),
-                    ..default()
-                },
-            ))
-            .id();
-
-        match transition_event.desired_transition {
-            TransitionOption::FadeToColor(desired_color) => {
-                commands
-                    .entity(transition)
-                    .remove::<Handle<FadeToColorMaterial>>()
-                    .insert(fade_to_color_mat.add(FadeToColorMaterial {
-                        time: 0.0,
-                        color: desired_color,
-                    }));
-            }
-            TransitionOption::FadeFromColor(desired_color) => {
-                commands
-                    .entity(transition)
-                    .remove::<Handle<FadeToColorMaterial>>()
-                    .insert(fade_from_color_mat.add(FadeFromColorMaterial {
-                        time: 1.0,
-                        color: desired_color,
-                    }));
-            }
-        }
+        spawn_transition_node(
+            &mut commands,
+            transition_event.desired_transition,
+            transition_event.layer,
+            true,
+            &mut materials,
+        );
     }
 }
 
+// real time, so transitions still play while the game is paused
 pub fn animate_transition(
     mut commands: Commands,
-    mut transition_node_query: Query<(Entity, &mut TransitionNode)>,
-    time: Res<Time>,
+    mut transition_node_query: Query<(Entity, &mut TransitionNode, TransitionMaterialHandles)>,
+    time: Res<Time<Real>>,
     mut fade_to_color_materials: ResMut<Assets<FadeToColorMaterial>>,
     mut fade_from_color_materials: ResMut<Assets<FadeFromColorMaterial>>,
+    mut shape_transition_materials: ResMut<Assets<ShapeTransitionMaterial>>,
 ) {
-    for (transition_entity, mut transition_node) in transition_node_query.iter_mut() {
+    for (
+        transition_entity,
+        mut transition_node,
+        (fade_to_handle, fade_from_handle, shape_handle),
+    ) in transition_node_query.iter_mut()
+    {
         transition_node.timer.tick(time.delta());
 
         // fade to color
-        for (_, material) in fade_to_color_materials.iter_mut() {
-            material.time = transition_node.timer.elapsed_secs(); // time goes 0.0 -> 1.0
+        if let Some(material) =
+            fade_to_handle.and_then(|handle| fade_to_color_materials.get_mut(handle))
+        {
+            material.time = transition_node.timer.fraction(); // time goes 0.0 -> 1.0
         }
 
         // fade from color
-        for (_, material) in fade_from_color_materials.iter_mut() {
-            material.time = transition_node.timer.remaining_secs(); // time goes 1.0 -> 0.0
+        if let Some(material) =
+            fade_from_handle.and_then(|handle| fade_from_color_materials.get_mut(handle))
+        {
+            material.time = transition_node.timer.fraction_remaining(); // time goes 1.0 -> 0.0
+        }
+
+        // shapes, the easing is applied in the shader
+        if let Some(material) =
+            shape_handle.and_then(|handle| shape_transition_materials.get_mut(handle))
+        {
+            material.time = transition_node.timer.fraction(); // time goes 0.0 -> 1.0
         }
 
-        if transition_node.timer.just_finished() {
+        if transition_node.timer.just_finished() && transition_node.despawn_on_finish {
             commands.entity(transition_entity).despawn_recursive();
         }
     }
 }
+
+// projects the iris centre every frame, so it stays on its world point while the camera moves
+pub fn update_iris_transitions(
+    iris_query: Query<(&IrisTarget, &Handle<ShapeTransitionMaterial>)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<WorldCamera>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut shape_transition_materials: ResMut<Assets<ShapeTransitionMaterial>>,
+) {
+    let (Ok((camera, camera_transform)), Ok(window)) =
+        (camera_query.get_single(), window_query.get_single())
+    else {
+        return;
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    let viewport_origin = camera
+        .logical_viewport_rect()
+        .map_or(Vec2::ZERO, |viewport| viewport.min);
+
+    for (iris_target, handle) in iris_query.iter() {
+        let Some(position) = camera.world_to_viewport(camera_transform, iris_target.world_position)
+        else {
+            continue;
+        };
+        if let Some(material) = shape_transition_materials.get_mut(handle) {
+            let centre = (viewport_origin + position) / window_size;
+            material.params = centre.extend(0.0).extend(0.0);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use super::transition::{
+    spawn_transition_node, TransitionMaterials, TransitionNode, TransitionOption,
+};
+use crate::{
+    config::controls::InputAction,
+    states::{
+        app_state::{AppState, EditAppState},
+        data_state::DataState,
+        options_state::{EditOptionsState, OptionsState},
+    },
+};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use leafwing_input_manager::plugin::ToggleActions;
+
+// screen changes cover any other transition that is still playing
+pub const SEQUENCE_LAYER: i32 = 100;
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// fade out -> switch states at full cover -> wait for the next screen's assets -> fade in
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SequencePhase {
+    #[default]
+    Idle,
+    FadingOut(SequenceTransition, Entity),
+    Switching(SequenceTransition, Entity),
+    Loading(SequenceTransition, Entity),
+    FadingIn(SequenceTransition, Entity),
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct TransitionSequencer {
+    pub phase: SequencePhase,
+}
+
+impl TransitionSequencer {
+    pub fn is_running(&self) -> bool {
+        self.phase != SequencePhase::Idle
+    }
+}
+
+// the states a sequence switches and then waits on before fading back in
+#[derive(SystemParam)]
+pub struct SequenceStates<'w> {
+    pub app_state: Res<'w, State<AppState>>,
+    pub options_state: Res<'w, State<OptionsState>>,
+    pub data_state: Res<'w, State<DataState>>,
+    pub next_data_state: Res<'w, NextState<DataState>>,
+}
+
+#[derive(SystemParam)]
+pub struct SequenceWriters<'w> {
+    pub edit_app_state: EventWriter<'w, EditAppState>,
+    pub edit_options_state: EventWriter<'w, EditOptionsState>,
+    pub transition_finished: EventWriter<'w, TransitionFinished>,
+}
+
+// events ----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// the state edits still go through the allowed changes validation once the screen is covered
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct SequenceTransition {
+    pub app_state: Option<AppState>,
+    pub options_state: Option<OptionsState>,
+    pub color: Vec4,
+}
+
+impl SequenceTransition {
+    pub fn to_app_state(app_state: AppState) -> Self {
+        SequenceTransition {
+            app_state: Some(app_state),
+            options_state: None,
+            color: Vec4::new(0.0, 0.0, 0.0, 1.0), // default BLACK
+        }
+    }
+}
+
+#[derive(Event, Debug)]
+pub struct TransitionFinished {
+    pub transition: SequenceTransition,
+}
+
+// run conditions --------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn is_transition_running(sequencer: Res<TransitionSequencer>) -> bool {
+    sequencer.is_running()
+}
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn start_transition_sequence(
+    mut commands: Commands,
+    mut sequencer: ResMut<TransitionSequencer>,
+    mut read_sequence_transition: EventReader<SequenceTransition>,
+    mut toggle_actions: ResMut<ToggleActions<InputAction>>,
+    mut materials: TransitionMaterials,
+) {
+    for transition in read_sequence_transition.read() {
+        if sequencer.is_running() {
+            warn!(
+                "SequenceTransition {:?} ignored, {:?} is still running",
+                transition, sequencer.phase
+            );
+            continue;
+        }
+
+        let cover = spawn_transition_node(
+            &mut commands,
+            TransitionOption::FadeToColor(transition.color),
+            SEQUENCE_LAYER,
+            false,
+            &mut materials,
+        );
+
+        // ui input stays blocked until the next screen has faded in
+        toggle_actions.enabled = false;
+        sequencer.phase = SequencePhase::FadingOut(*transition, cover);
+        debug!("SequenceTransition started: {:?}", transition);
+    }
+}
+
+pub fn advance_transition_sequence(
+    mut commands: Commands,
+    mut sequencer: ResMut<TransitionSequencer>,
+    transition_node_query: Query<&TransitionNode>,
+    states: SequenceStates,
+    mut toggle_actions: ResMut<ToggleActions<InputAction>>,
+    mut writers: SequenceWriters,
+    mut materials: TransitionMaterials,
+) {
+    // a node that has gone missing counts as finished, so a sequence can never get stuck
+    let is_finished = |node: Entity| {
+        transition_node_query
+            .get(node)
+            .map_or(true, |transition_node| transition_node.timer.finished())
+    };
+
+    match sequencer.phase {
+        SequencePhase::Idle => {}
+        SequencePhase::FadingOut(transition, cover) => {
+            if !is_finished(cover) {
+                return;
+            }
+
+            if let Some(desired_options_state) = transition.options_state {
+                writers.edit_options_state.send(EditOptionsState {
+                    desired_options_state,
+                });
+            }
+            if let Some(desired_app_state) = transition.app_state {
+                writers
+                    .edit_app_state
+                    .send(EditAppState { desired_app_state });
+            }
+            sequencer.phase = SequencePhase::Switching(transition, cover);
+        }
+        SequencePhase::Switching(transition, cover) => {
+            // the edits were handled last frame, their states have been applied by now
+            let is_app_state_refused = transition
+                .app_state
+                .is_some_and(|desired| desired != *states.app_state.get());
+            let is_options_state_refused = transition
+                .options_state
+                .is_some_and(|desired| desired != *states.options_state.get());
+            if is_app_state_refused || is_options_state_refused {
+                warn!(
+                    "SequenceTransition {:?} was refused, fading back in on {:?} / {:?}",
+                    transition,
+                    states.app_state.get(),
+                    states.options_state.get()
+                );
+            }
+            sequencer.phase = SequencePhase::Loading(transition, cover);
+        }
+        SequencePhase::Loading(transition, cover) => {
+            if *states.data_state.get() != DataState::Idle || states.next_data_state.0.is_some() {
+                return;
+            }
+
+            if let Some(entity) = commands.get_entity(cover) {
+                entity.despawn_recursive();
+            }
+            let reveal = spawn_transition_node(
+                &mut commands,
+                TransitionOption::FadeFromColor(transition.color),
+                SEQUENCE_LAYER,
+                false,
+                &mut materials,
+            );
+            sequencer.phase = SequencePhase::FadingIn(transition, reveal);
+        }
+        SequencePhase::FadingIn(transition, reveal) => {
+            if !is_finished(reveal) {
+                return;
+            }
+
+            if let Some(entity) = commands.get_entity(reveal) {
+                entity.despawn_recursive();
+            }
+            toggle_actions.enabled = true;
+            sequencer.phase = SequencePhase::Idle;
+            writers
+                .transition_finished
+                .send(TransitionFinished { transition });
+        }
+    }
+}
+
+pub fn log_transition_finished(mut read_transition_finished: EventReader<TransitionFinished>) {
+    for transition_finished in read_transition_finished.read() {
+        debug!(
+            "SequenceTransition finished: {:?}",
+            transition_finished.transition
+        );
+    }
+}
@@ -1,4 +1,6 @@
+mod audio;
 mod config;
+mod data;
 mod game;
 mod graphics;
 pub mod scene_directory;
@@ -6,7 +8,9 @@ mod states;
 pub mod style;
 mod ui;
 
+use audio::audio_manager;
 use config::config_manager;
+use data::data_manager;
 use game::game_manager;
 use graphics::graphics_manager;
 use states::state_manager;
@@ -52,6 +56,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         // my plugins
         .add_plugins((
             config_manager::ConfigManagerPlugin,
+            audio_manager::AudioManagerPlugin,
+            data_manager::DataManagerPlugin,
             game_manager::GameManagerPlugin,
             graphics_manager::GraphicsManagerPlugin,
             state_manager::StateManagerPlugin,
@@ -1,6 +1,7 @@
 use super::options_state::OptionsState;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 // resources -------------------------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -20,18 +21,26 @@ impl Default for AppStateAllowedChanges {
         AppStateAllowedChanges {
             splash: vec![AppState::Title],
             title: vec![AppState::Options, AppState::Credits, AppState::LoadSave],
-            options: vec![AppState::Title],
+            options: vec![AppState::Title, AppState::Game], // only back to its origin
             credits: vec![AppState::Title],
             loadsave: vec![AppState::Title, AppState::Game],
-            game: vec![AppState::Fail, AppState::Title],
+            game: vec![AppState::Fail, AppState::Title, AppState::Options],
             fail: vec![AppState::Game, AppState::Title],
         }
     }
 }
 
+// where the current return-to-origin state was opened from, see is_return_to_origin()
+#[derive(Resource, Debug, Default)]
+pub struct AppStateOrigin {
+    pub origin: Option<AppState>,
+    // true from leaving a return-to-origin state until its origin has been re-entered
+    pub is_returning: bool,
+}
+
 // states ----------------------------------------------------------------------
 // -----------------------------------------------------------------------------
-#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Serialize, Deserialize)]
 pub enum AppState {
     #[default]
     Splash,
@@ -50,11 +59,32 @@ pub struct EditAppState {
     pub desired_app_state: AppState,
 }
 
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// states opened on top of another screen that can only be left back to it
+//  ie: Options opened from the pause menu returns to the game, not the title
+pub fn is_return_to_origin(app_state: AppState) -> bool {
+    matches!(app_state, AppState::Options)
+}
+
+// run conditions --------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// the game was left for a return-to-origin state, its world is kept until it comes back
+pub fn is_game_suspended(origin: Res<AppStateOrigin>) -> bool {
+    origin.origin == Some(AppState::Game)
+}
+
+// skips the setup of a state that is being returned to, it was never torn down
+pub fn is_returning_to_origin(origin: Res<AppStateOrigin>) -> bool {
+    origin.is_returning
+}
+
 // systems ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 pub fn handle_edit_app_state(
     current_app_state: Res<State<AppState>>,
     allowed_changes: Res<AppStateAllowedChanges>,
+    mut origin: ResMut<AppStateOrigin>,
     mut next_app_state: ResMut<NextState<AppState>>,
     mut read_edit_app_state: EventReader<EditAppState>,
     mut next_options_state: ResMut<NextState<OptionsState>>,
@@ -72,7 +102,15 @@ pub fn handle_edit_app_state(
             AppState::Fail => &allowed_changes.fail,
         })
         .contains(&app_state_edit.desired_app_state)
+            && (!is_return_to_origin(*current_app_state.get())
+                || origin.origin.unwrap_or(AppState::Title) == app_state_edit.desired_app_state)
         {
+            if is_return_to_origin(app_state_edit.desired_app_state) {
+                origin.origin = Some(*current_app_state.get());
+            } else if is_return_to_origin(*current_app_state.get()) {
+                origin.origin = None;
+                origin.is_returning = true;
+            }
             if app_state_edit.desired_app_state == AppState::Options {
                 next_options_state.set(OptionsState::Menu);
             };
@@ -85,3 +123,13 @@ pub fn handle_edit_app_state(
         }
     }
 }
+
+pub fn finish_return_to_origin(
+    current_app_state: Res<State<AppState>>,
+    mut origin: ResMut<AppStateOrigin>,
+) {
+    // Update has run once in the origin state, its setup systems have been skipped
+    if origin.is_returning && !is_return_to_origin(*current_app_state.get()) {
+        origin.is_returning = false;
+    }
+}
@@ -1,16 +1,18 @@
-#![allow(unused)]
-// TODO: remove allow(unused) upon implementing persistent data functionality
+use crate::config::controls::InputAction;
 
 use bevy::prelude::*;
+use leafwing_input_manager::action_state::ActionState;
+use serde::Deserialize;
 
 // states ----------------------------------------------------------------------
 // -----------------------------------------------------------------------------
-#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Deserialize)]
 pub enum DataState {
     Idle,
     #[default]
-    Loading, // assets
-    Saving, // scenes
+    Loading, // boot assets, during splash screen
+    LoadingGame, // game assets, behind loading screen
+    Saving,      // scenes
 }
 
 // events ----------------------------------------------------------------------
@@ -22,6 +24,17 @@ pub struct EditDataState {
 
 // systems ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
+pub fn emit_edit_data_state(
+    action_state: Res<ActionState<InputAction>>,
+    mut write_edit_data_state: EventWriter<EditDataState>,
+) {
+    if action_state.just_pressed(&InputAction::QuickSave) {
+        write_edit_data_state.send(EditDataState {
+            desired_data_state: DataState::Saving,
+        });
+    }
+}
+
 pub fn handle_edit_data_state(
     mut next_data_state: ResMut<NextState<DataState>>,
     mut read_edit_data_state: EventReader<EditDataState>,
@@ -1,8 +1,12 @@
+use crate::config::controls::InputAction;
+
 use bevy::prelude::*;
+use leafwing_input_manager::action_state::ActionState;
+use serde::{Deserialize, Serialize};
 
 // states ----------------------------------------------------------------------
 // -----------------------------------------------------------------------------
-#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Serialize, Deserialize)]
 pub enum GameState {
     #[default]
     Playing,
@@ -12,17 +16,25 @@ pub enum GameState {
 // events ----------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 #[derive(Event)]
-pub struct EditGameState;
+pub struct EditGameState {
+    pub desired_game_state: GameState,
+}
 
 // systems ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 pub fn emit_edit_game_state(
-    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    game_state: Res<State<GameState>>,
+    action_state: Res<ActionState<InputAction>>,
     mut write_edit_game_state: EventWriter<EditGameState>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Space) {
-        write_edit_game_state.send(EditGameState {});
-        keyboard_input.reset(KeyCode::Space);
+    // Back opens the pause menu and closes it again
+    //  Space is not checked here, it is also Select and would press the focused pause button
+    if action_state.just_pressed(&InputAction::Back) {
+        let desired_game_state = match *game_state.get() {
+            GameState::Playing => GameState::Paused,
+            GameState::Paused => GameState::Playing,
+        };
+        write_edit_game_state.send(EditGameState { desired_game_state });
     }
 }
 
@@ -31,16 +43,33 @@ pub fn handle_edit_game_state(
     game_state: Res<State<GameState>>,
     mut read_edit_game_state: EventReader<EditGameState>,
 ) {
-    for _ in read_edit_game_state.read() {
-        match *game_state.get() {
-            GameState::Playing => {
-                commands.insert_resource(NextState(Some(GameState::Paused)));
-                info!("GameState change: Playing -> Paused");
-            }
-            GameState::Paused => {
-                commands.insert_resource(NextState(Some(GameState::Playing)));
-                info!("GameState change: Paused -> Playing");
-            }
+    for game_state_edit in read_edit_game_state.read() {
+        if *game_state.get() != game_state_edit.desired_game_state {
+            commands.insert_resource(NextState(Some(game_state_edit.desired_game_state)));
+            info!(
+                "GameState change: {:?} -> {:?}",
+                game_state.get(),
+                game_state_edit.desired_game_state
+            );
         }
     }
 }
+
+// gameplay runs on virtual time and freezes, menus keep animating on real time
+pub fn pause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+pub fn resume_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+// a game left from the pause menu starts again unpaused
+pub fn reset_game_state(
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    if *game_state.get() != GameState::Playing {
+        next_game_state.set(GameState::Playing);
+    }
+}
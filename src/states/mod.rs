@@ -1,6 +1,7 @@
 pub mod app_state;
 pub mod data_state;
 pub mod game_state;
+pub mod navigation;
 pub mod options_state;
 pub mod profile_state;
 pub mod state_manager;
@@ -0,0 +1,103 @@
+use super::{
+    app_state::AppState,
+    options_state::{EditOptionsState, OptionsState},
+};
+use crate::{config::controls::InputAction, graphics::transition_sequence::SequenceTransition};
+
+use bevy::prelude::*;
+use leafwing_input_manager::action_state::ActionState;
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// every menu screen visited since the last root screen, the current screen is last
+#[derive(Resource, Debug, Default)]
+pub struct NavigationHistory {
+    pub screens: Vec<(AppState, OptionsState)>,
+}
+
+impl NavigationHistory {
+    pub fn previous(&self) -> Option<(AppState, OptionsState)> {
+        self.screens.iter().rev().nth(1).copied()
+    }
+}
+
+// events ----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Event)]
+pub struct NavigateBack;
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// back never leaves a root screen, it starts a new history instead
+pub fn is_root_screen(app_state: AppState) -> bool {
+    matches!(app_state, AppState::Title | AppState::Game | AppState::Fail)
+}
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn track_navigation_history(
+    app_state: Res<State<AppState>>,
+    options_state: Res<State<OptionsState>>,
+    mut history: ResMut<NavigationHistory>,
+) {
+    let screen = (*app_state.get(), *options_state.get());
+
+    if is_root_screen(screen.0) {
+        history.screens.clear();
+    }
+
+    // revisiting a screen already in the history drops everything after it
+    match history
+        .screens
+        .iter()
+        .position(|visited| *visited == screen)
+    {
+        Some(index) => history.screens.truncate(index + 1),
+        None => history.screens.push(screen),
+    }
+    debug!("NavigationHistory: {:?}", history.screens);
+}
+
+pub fn emit_navigate_back(
+    action_state: Res<ActionState<InputAction>>,
+    mut write_navigate_back: EventWriter<NavigateBack>,
+) {
+    if action_state.just_pressed(&InputAction::Back) {
+        write_navigate_back.send(NavigateBack);
+    }
+}
+
+pub fn handle_navigate_back(
+    app_state: Res<State<AppState>>,
+    options_state: Res<State<OptionsState>>,
+    history: Res<NavigationHistory>,
+    mut read_navigate_back: EventReader<NavigateBack>,
+    mut write_edit_options_state: EventWriter<EditOptionsState>,
+    mut write_sequence_transition: EventWriter<SequenceTransition>,
+) {
+    // several back presses in the same frame only go back one screen
+    if read_navigate_back.read().count() == 0 {
+        return;
+    }
+
+    let Some((previous_app_state, previous_options_state)) = history.previous() else {
+        debug!("NavigateBack ignored, no previous screen in history");
+        return;
+    };
+
+    // both edits still go through the allowed changes validation
+    //  switch OptionsState as well when leaving AppState::Options to avoid scheduling conflicts
+    //  screen changes fade through the sequencer, moving within the options menus is instant
+    let desired_options_state =
+        (previous_options_state != *options_state.get()).then_some(previous_options_state);
+    if previous_app_state != *app_state.get() {
+        write_sequence_transition.send(SequenceTransition {
+            options_state: desired_options_state,
+            ..SequenceTransition::to_app_state(previous_app_state)
+        });
+    } else if let Some(desired_options_state) = desired_options_state {
+        write_edit_options_state.send(EditOptionsState {
+            desired_options_state,
+        });
+    }
+}
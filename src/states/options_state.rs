@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 // resources -------------------------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -33,7 +34,7 @@ impl Default for OptionsStateAllowedChanges {
 
 // states ----------------------------------------------------------------------
 // -----------------------------------------------------------------------------
-#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Serialize, Deserialize)]
 pub enum OptionsState {
     #[default]
     None,
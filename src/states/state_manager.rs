@@ -1,4 +1,5 @@
-use super::{app_state, data_state, game_state, options_state, profile_state};
+use super::{app_state, data_state, game_state, navigation, options_state, profile_state};
+use crate::graphics::transition_sequence;
 
 use bevy::prelude::*;
 
@@ -9,7 +10,9 @@ pub struct StateManagerPlugin;
 impl Plugin for StateManagerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<app_state::AppStateAllowedChanges>()
+            .init_resource::<app_state::AppStateOrigin>()
             .init_resource::<options_state::OptionsStateAllowedChanges>()
+            .init_resource::<navigation::NavigationHistory>()
             .init_state::<app_state::AppState>()
             .init_state::<game_state::GameState>()
             .init_state::<options_state::OptionsState>()
@@ -20,19 +23,48 @@ impl Plugin for StateManagerPlugin {
             .add_event::<options_state::EditOptionsState>()
             .add_event::<data_state::EditDataState>()
             .add_event::<profile_state::EditProfileState>()
+            .add_event::<navigation::NavigateBack>()
+            .add_systems(
+                OnEnter(game_state::GameState::Paused),
+                game_state::pause_virtual_time,
+            )
+            .add_systems(
+                OnExit(game_state::GameState::Paused),
+                game_state::resume_virtual_time,
+            )
+            .add_systems(
+                OnExit(app_state::AppState::Game),
+                game_state::reset_game_state.run_if(not(app_state::is_game_suspended)),
+            )
+            .add_systems(PostUpdate, app_state::finish_return_to_origin)
             .add_systems(
                 Update,
                 (
                     app_state::handle_edit_app_state
                         .after(profile_state::handle_edit_profile_state),
-                    game_state::emit_edit_game_state,
+                    game_state::emit_edit_game_state
+                        .run_if(in_state(app_state::AppState::Game))
+                        .run_if(not(transition_sequence::is_transition_running)),
                     game_state::handle_edit_game_state.run_if(in_state(app_state::AppState::Game)),
                     options_state::handle_edit_options_state,
-                    data_state::handle_edit_data_state
-                        .run_if(in_state(app_state::AppState::Game))
-                        .run_if(in_state(app_state::AppState::Options)),
+                    data_state::emit_edit_data_state.run_if(in_state(app_state::AppState::Game)),
+                    data_state::handle_edit_data_state.run_if(
+                        in_state(app_state::AppState::Game)
+                            .or_else(in_state(app_state::AppState::Options)),
+                    ),
                     profile_state::handle_edit_profile_state
                         .run_if(in_state(app_state::AppState::LoadSave)),
+                    navigation::track_navigation_history.run_if(
+                        state_changed::<app_state::AppState>
+                            .or_else(state_changed::<options_state::OptionsState>),
+                    ),
+                    navigation::emit_navigate_back
+                        .run_if(not(in_state(app_state::AppState::Splash)))
+                        .run_if(not(in_state(app_state::AppState::Game))),
+                    navigation::handle_navigate_back
+                        .after(navigation::emit_navigate_back)
+                        .before(app_state::handle_edit_app_state)
+                        .before(options_state::handle_edit_options_state),
                 ),
             );
     }
@@ -1,12 +1,24 @@
-#![allow(unused)]
+use bevy::prelude::*;
+use bevy_asset_loader::prelude::*;
 
 // FONTS -----------------------------------------------------------------------
 // -----------------------------------------------------------------------------
-pub const FONT_HEADLINE: &str = "fonts/Roboto_Slab/static/RobotoSlab-SemiBold.ttf";
+// preloaded during DataState::Loading, see data::asset_collections
+#[derive(AssetCollection, Resource)]
+pub struct FontAssets {
+    #[asset(path = "fonts/Roboto_Slab/static/RobotoSlab-SemiBold.ttf")]
+    pub headline: Handle<Font>,
 
-pub const FONT_BODY: &str = "fonts/Roboto/Roboto-Medium.ttf";
-pub const FONT_BODY_ITALIC: &str = "fonts/Roboto/Roboto-MediumItalic.ttf";
-pub const FONT_BODY_BOLD: &str = "fonts/Roboto/Roboto-Bold.ttf";
-pub const FONT_BODY_BOLD_ITALIC: &str = "fonts/Roboto/Roboto-BoldItalic.ttf";
-pub const FONT_BODY_LIGHT: &str = "fonts/Roboto/Roboto-Light.ttf";
-pub const FONT_BODY_LIGHT_ITALIC: &str = "fonts/Roboto/Roboto-LightItalic.ttf";
+    #[asset(path = "fonts/Roboto/Roboto-Medium.ttf")]
+    pub body: Handle<Font>,
+    #[asset(path = "fonts/Roboto/Roboto-MediumItalic.ttf")]
+    pub body_italic: Handle<Font>,
+    #[asset(path = "fonts/Roboto/Roboto-Bold.ttf")]
+    pub body_bold: Handle<Font>,
+    #[asset(path = "fonts/Roboto/Roboto-BoldItalic.ttf")]
+    pub body_bold_italic: Handle<Font>,
+    #[asset(path = "fonts/Roboto/Roboto-Light.ttf")]
+    pub body_light: Handle<Font>,
+    #[asset(path = "fonts/Roboto/Roboto-LightItalic.ttf")]
+    pub body_light_italic: Handle<Font>,
+}
@@ -1,8 +1,9 @@
 #![allow(unused)]
 
-use super::{style_colors, style_fonts};
+use super::{style_colors, style_fonts::FontAssets};
 
 use bevy::prelude::*;
+use bevy_asset_loader::prelude::*;
 
 // CLEAR COLORS ----------------------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -22,15 +23,26 @@ pub const NORMAL_TEXT_COLOR: Color = style_colors::LATTE_TEXT;
 pub const FOCUSED_TEXT_COLOR: Color = style_colors::LATTE_RED;
 pub const PRESSED_TEXT_COLOR: Color = style_colors::LATTE_GREEN;
 
+pub const OVERLAY_COLOR: Color = Color::rgba(0.14, 0.15, 0.20, 0.75); // FRAPPE_CRUST
+
 // UI SIZES --------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 pub const NORMAL_TEXT_SIZE: f32 = 60.0;
 pub const FOCUSED_TEXT_SIZE: f32 = 100.0;
+pub const DETAIL_TEXT_SIZE: f32 = 28.0;
 
 // UI MATERIAL SHADERS ---------------------------------------------------------
 // -----------------------------------------------------------------------------
 pub const SHADER_COLOR: Color = Color::NONE;
-pub const SHADER_BUTTON_TEXTURE: &str = "images/white.png";
+
+// UI TEXTURES -----------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// preloaded during DataState::Loading, see data::asset_collections
+#[derive(AssetCollection, Resource)]
+pub struct UiAssets {
+    #[asset(path = "images/white.png")]
+    pub button_texture: Handle<Image>,
+}
 
 // UI ENTITY SPAWN HELPERS -----------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -60,13 +72,34 @@ pub fn button_style() -> Style {
     }
 }
 
-pub fn text_bundle(asset_server: &Res<AssetServer>, text: String) -> TextBundle {
+pub fn loading_bar_style() -> Style {
+    Style {
+        width: Val::Px(600.0),
+        height: Val::Px(40.0),
+        border: UiRect::all(Val::Px(6.0)),
+        margin: UiRect::all(Val::Px(20.0)),
+        ..default()
+    }
+}
+
+pub fn text_bundle(fonts: &FontAssets, text: String) -> TextBundle {
     TextBundle::from_section(
         text,
         TextStyle {
-            font: asset_server.load(style_fonts::FONT_BODY),
+            font: fonts.body.clone(),
             font_size: NORMAL_TEXT_SIZE,
             color: NORMAL_TEXT_COLOR,
         },
     )
 }
+
+pub fn detail_text_bundle(fonts: &FontAssets, text: String) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font: fonts.body_light.clone(),
+            font_size: DETAIL_TEXT_SIZE,
+            color: NORMAL_TEXT_COLOR,
+        },
+    )
+}
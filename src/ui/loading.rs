@@ -0,0 +1,94 @@
+use crate::{
+    states::data_state::DataState,
+    style::{
+        style_fonts::FontAssets,
+        style_ui::{self, UiAssets},
+    },
+};
+
+use bevy::prelude::*;
+use iyes_progress::ProgressCounter;
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct LoadingScreenUIPlugin;
+
+impl Plugin for LoadingScreenUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(DataState::LoadingGame), spawn_loading)
+            .add_systems(OnExit(DataState::LoadingGame), despawn_loading)
+            .add_systems(
+                Update,
+                animate_loading_bar.run_if(in_state(DataState::LoadingGame)),
+            );
+    }
+}
+
+// components ------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Component)]
+pub struct LoadingNode;
+
+#[derive(Component)]
+pub struct LoadingBar;
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn spawn_loading(mut commands: Commands, fonts: Res<FontAssets>, ui_assets: Res<UiAssets>) {
+    commands
+        .spawn((
+            Name::new("LoadingNode"),
+            LoadingNode,
+            style_ui::node_bundle(),
+        ))
+        .with_children(|parent| {
+            parent.spawn(style_ui::text_bundle(&fonts, "Loading".to_string()));
+
+            // progress bar track, filled from the left as collections finish loading
+            parent
+                .spawn(NodeBundle {
+                    style: style_ui::loading_bar_style(),
+                    background_color: style_ui::NORMAL_BORDER_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        LoadingBar,
+                        ImageBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            image: UiImage::new(ui_assets.button_texture.clone()),
+                            background_color: style_ui::NORMAL_BUTTON_COLOR.into(),
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
+
+pub fn despawn_loading(mut commands: Commands, loading_query: Query<Entity, With<LoadingNode>>) {
+    for loading_entity in loading_query.iter() {
+        commands.entity(loading_entity).despawn_recursive();
+    }
+}
+
+pub fn animate_loading_bar(
+    progress_counter: Option<Res<ProgressCounter>>,
+    mut bar_query: Query<&mut Style, With<LoadingBar>>,
+) {
+    let Some(progress_counter) = progress_counter else {
+        return;
+    };
+    let progress = progress_counter.progress();
+    if progress.total == 0 {
+        return;
+    }
+
+    let percent: f32 = f32::from(progress) * 100.0;
+    for mut bar_style in bar_query.iter_mut() {
+        bar_style.width = Val::Percent(percent);
+    }
+}
@@ -0,0 +1,513 @@
+use super::ui_manager::{
+    in_menu, AutoNavigation, InitialUiFocus, UiElement, UiFocusable, UiNavigation,
+};
+use crate::{
+    config::controls,
+    graphics::{
+        materials::materials_ui::NormalButtonMaterial, transition_sequence::SequenceTransition,
+    },
+    states::{
+        app_state::AppState,
+        data_state::{DataState, EditDataState},
+        game_state::{EditGameState, GameState},
+        navigation::NavigateBack,
+        options_state::{EditOptionsState, OptionsState},
+    },
+    style::{
+        style_fonts::FontAssets,
+        style_ui::{self, UiAssets},
+    },
+};
+
+use bevy::{
+    app::AppExit,
+    asset::{io::Reader, ron, AssetLoader, AsyncReadExt, LoadContext},
+    ecs::system::SystemParam,
+    prelude::*,
+    utils::BoxedFuture,
+};
+use bevy_asset_loader::prelude::*;
+use leafwing_input_manager::action_state::ActionState;
+use serde::Deserialize;
+use thiserror::Error;
+
+// plugins ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub struct MenuScreensUIPlugin;
+
+impl Plugin for MenuScreensUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<MenuDefinition>()
+            .init_asset_loader::<MenuDefinitionLoader>()
+            .add_event::<MenuEvent>()
+            .add_systems(OnEnter(AppState::Title), spawn_menu(MenuScreen::Title))
+            .add_systems(OnExit(AppState::Title), despawn_menu(MenuScreen::Title))
+            .add_systems(OnEnter(AppState::Credits), spawn_menu(MenuScreen::Credits))
+            .add_systems(OnExit(AppState::Credits), despawn_menu(MenuScreen::Credits))
+            .add_systems(
+                OnEnter(AppState::LoadSave),
+                spawn_menu(MenuScreen::LoadSave),
+            )
+            .add_systems(
+                OnExit(AppState::LoadSave),
+                despawn_menu(MenuScreen::LoadSave),
+            )
+            .add_systems(OnEnter(AppState::Fail), spawn_menu(MenuScreen::Gameover))
+            .add_systems(OnExit(AppState::Fail), despawn_menu(MenuScreen::Gameover))
+            .add_systems(
+                OnEnter(OptionsState::Menu),
+                spawn_menu(MenuScreen::OptionsMenu),
+            )
+            .add_systems(
+                OnExit(OptionsState::Menu),
+                despawn_menu(MenuScreen::OptionsMenu),
+            )
+            .add_systems(
+                OnEnter(OptionsState::General),
+                spawn_menu(MenuScreen::GeneralOptions),
+            )
+            .add_systems(
+                OnExit(OptionsState::General),
+                despawn_menu(MenuScreen::GeneralOptions),
+            )
+            .add_systems(OnEnter(GameState::Paused), spawn_menu(MenuScreen::Pause))
+            .add_systems(OnExit(GameState::Paused), despawn_menu(MenuScreen::Pause))
+            // the game stays paused while Options is opened from the pause menu
+            .add_systems(OnExit(AppState::Game), despawn_menu(MenuScreen::Pause))
+            .add_systems(
+                OnEnter(AppState::Game),
+                spawn_menu(MenuScreen::Pause).run_if(in_state(GameState::Paused)),
+            )
+            .add_systems(
+                Update,
+                (
+                    // boot assets used for building are still loading during the splash screen
+                    build_menus.run_if(not(in_state(AppState::Splash))),
+                    handle_ui_selection.run_if(in_menu),
+                    log_menu_events,
+                ),
+            );
+    }
+}
+
+// assets ----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// a menu screen described in assets/menus/*.menu.ron, rebuilt when the file changes
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct MenuDefinition {
+    // id of the button focused the first time the menu is opened
+    #[serde(default)]
+    pub initial_focus: Option<String>,
+    // moving past the first / last button wraps around to the other side
+    #[serde(default)]
+    pub wrap: bool,
+    pub elements: Vec<MenuElementDefinition>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum MenuElementDefinition {
+    Button {
+        id: String,
+        text: String,
+        #[serde(default)]
+        detail: Option<String>,
+        action: MenuAction,
+        // explicit links by button id, directions left out are resolved from the layout
+        #[serde(default)]
+        navigation: MenuNavigationDefinition,
+    },
+    Label {
+        text: String,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct MenuNavigationDefinition {
+    #[serde(default)]
+    pub up: Option<String>,
+    #[serde(default)]
+    pub down: Option<String>,
+    #[serde(default)]
+    pub left: Option<String>,
+    #[serde(default)]
+    pub right: Option<String>,
+}
+
+// state changes still go through the allowed changes validation
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum MenuAction {
+    AppState(AppState),
+    OptionsState(OptionsState),
+    GameState(GameState),
+    DataState(DataState),
+    Back,
+    Quit,
+    // sent as a MenuEvent for systems outside the ui to react to
+    Event(String),
+}
+
+#[derive(Default)]
+pub struct MenuDefinitionLoader;
+
+#[derive(Debug, Error)]
+pub enum MenuDefinitionLoaderError {
+    #[error("Could not read menu definition: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse menu definition: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for MenuDefinitionLoader {
+    type Asset = MenuDefinition;
+    type Settings = ();
+    type Error = MenuDefinitionLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<MenuDefinition, MenuDefinitionLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(ron::de::from_bytes::<MenuDefinition>(&bytes)?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["menu.ron"]
+    }
+}
+
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// preloaded during DataState::Loading, see data::asset_collections
+#[derive(AssetCollection, Resource)]
+pub struct MenuAssets {
+    #[asset(path = "menus/title.menu.ron")]
+    pub title: Handle<MenuDefinition>,
+    #[asset(path = "menus/credits.menu.ron")]
+    pub credits: Handle<MenuDefinition>,
+    #[asset(path = "menus/loadsave.menu.ron")]
+    pub loadsave: Handle<MenuDefinition>,
+    #[asset(path = "menus/gameover.menu.ron")]
+    pub gameover: Handle<MenuDefinition>,
+    #[asset(path = "menus/options.menu.ron")]
+    pub options_menu: Handle<MenuDefinition>,
+    #[asset(path = "menus/general_options.menu.ron")]
+    pub general_options: Handle<MenuDefinition>,
+    #[asset(path = "menus/pause.menu.ron")]
+    pub pause: Handle<MenuDefinition>,
+}
+
+// every event a MenuAction can send
+#[derive(SystemParam)]
+pub struct MenuActionWriters<'w> {
+    pub sequence_transition: EventWriter<'w, SequenceTransition>,
+    pub edit_options_state: EventWriter<'w, EditOptionsState>,
+    pub edit_game_state: EventWriter<'w, EditGameState>,
+    pub edit_data_state: EventWriter<'w, EditDataState>,
+    pub navigate_back: EventWriter<'w, NavigateBack>,
+    pub menu_event: EventWriter<'w, MenuEvent>,
+    pub app_exit: EventWriter<'w, AppExit>,
+}
+
+// every asset build_menus reads to spawn a menu screen
+#[derive(SystemParam)]
+pub struct MenuBuildAssets<'w> {
+    pub fonts: Res<'w, FontAssets>,
+    pub ui_assets: Res<'w, UiAssets>,
+    pub menu_assets: Res<'w, MenuAssets>,
+    pub menu_definitions: Res<'w, Assets<MenuDefinition>>,
+    pub normal_button_mat: ResMut<'w, Assets<NormalButtonMaterial>>,
+}
+
+// components ------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MenuScreen {
+    Title,
+    Credits,
+    LoadSave,
+    Gameover,
+    OptionsMenu,
+    GeneralOptions,
+    Pause,
+}
+
+impl MenuScreen {
+    pub fn definition<'a>(&self, menu_assets: &'a MenuAssets) -> &'a Handle<MenuDefinition> {
+        match self {
+            MenuScreen::Title => &menu_assets.title,
+            MenuScreen::Credits => &menu_assets.credits,
+            MenuScreen::LoadSave => &menu_assets.loadsave,
+            MenuScreen::Gameover => &menu_assets.gameover,
+            MenuScreen::OptionsMenu => &menu_assets.options_menu,
+            MenuScreen::GeneralOptions => &menu_assets.general_options,
+            MenuScreen::Pause => &menu_assets.pause,
+        }
+    }
+
+    // drawn over the game world instead of replacing the screen
+    pub fn is_overlay(&self) -> bool {
+        matches!(self, MenuScreen::Pause)
+    }
+}
+
+// the elements are (re)built by build_menus once the definition is available
+#[derive(Component)]
+pub struct MenuRoot {
+    pub screen: MenuScreen,
+}
+
+#[derive(Component, Debug)]
+pub struct MenuButton {
+    pub screen: MenuScreen,
+    pub id: String,
+    pub action: MenuAction,
+}
+
+// the text entities under a MenuButton, screens update them through these markers
+#[derive(Component)]
+pub struct MenuButtonLabel;
+
+#[derive(Component)]
+pub struct MenuButtonDetail;
+
+// events ----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Event, Debug)]
+pub struct MenuEvent {
+    pub screen: MenuScreen,
+    pub name: String,
+}
+
+// systems ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+pub fn spawn_menu(screen: MenuScreen) -> impl Fn(Commands) {
+    move |mut commands: Commands| {
+        let background_color = if screen.is_overlay() {
+            style_ui::OVERLAY_COLOR
+        } else {
+            Color::NONE
+        };
+
+        commands.spawn((
+            Name::new(format!("{:?}Node", screen)),
+            MenuRoot { screen },
+            NodeBundle {
+                background_color: background_color.into(),
+                ..style_ui::node_bundle()
+            },
+        ));
+    }
+}
+
+pub fn despawn_menu(screen: MenuScreen) -> impl Fn(Commands, Query<(Entity, &MenuRoot)>) {
+    move |mut commands: Commands, menu_query: Query<(Entity, &MenuRoot)>| {
+        for (menu_entity, menu_root) in menu_query.iter() {
+            if menu_root.screen == screen {
+                commands.entity(menu_entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+pub fn build_menus(
+    mut commands: Commands,
+    mut assets: MenuBuildAssets,
+    initial_focus: Res<InitialUiFocus>,
+    mut read_asset_events: EventReader<AssetEvent<MenuDefinition>>,
+    menu_query: Query<(Entity, Ref<MenuRoot>)>,
+    button_query: Query<(&MenuButton, &UiFocusable)>,
+) {
+    // hot reloaded definitions rebuild every open menu that uses them
+    let changed_definitions: Vec<AssetId<MenuDefinition>> = read_asset_events
+        .read()
+        .filter_map(|asset_event| match asset_event {
+            AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (menu_entity, menu_root) in menu_query.iter() {
+        let screen = menu_root.screen;
+        let handle = screen.definition(&assets.menu_assets);
+        if !menu_root.is_added() && !changed_definitions.contains(&handle.id()) {
+            continue;
+        }
+        let Some(definition) = assets.menu_definitions.get(handle) else {
+            warn!("Menu definition for {:?} is not loaded", screen);
+            continue;
+        };
+
+        // keep the focused button across a reload, otherwise use the remembered focus
+        let focus_id: Option<String> = button_query
+            .iter()
+            .find(|(button, focusable)| button.screen == screen && focusable.is_focused)
+            .map(|(button, _)| button.id.clone())
+            .or_else(|| initial_focus.menus.get(&screen).cloned())
+            .or_else(|| definition.initial_focus.clone());
+
+        commands.entity(menu_entity).despawn_descendants();
+
+        let button_ids: Vec<&String> = definition
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                MenuElementDefinition::Button { id, .. } => Some(id),
+                MenuElementDefinition::Label { .. } => None,
+            })
+            .collect();
+        let ui_element = |id: &Option<String>| match id {
+            Some(id) => match button_ids.iter().position(|button_id| *button_id == id) {
+                Some(index) => UiElement::Menu(screen, index),
+                None => {
+                    warn!("Menu {:?} links to unknown button id {:?}", screen, id);
+                    UiElement::None
+                }
+            },
+            None => UiElement::None,
+        };
+        let focus_index = focus_id
+            .and_then(|focus_id| button_ids.iter().position(|id| **id == focus_id))
+            .unwrap_or(0);
+
+        let mut children: Vec<Entity> = Vec::new();
+        let mut button_index = 0;
+        for element in definition.elements.iter() {
+            match element {
+                MenuElementDefinition::Button {
+                    id,
+                    text,
+                    detail,
+                    action,
+                    navigation,
+                } => {
+                    let button = commands
+                        .spawn((
+                            MenuButton {
+                                screen,
+                                id: id.clone(),
+                                action: action.clone(),
+                            },
+                            UiFocusable {
+                                is_focused: button_index == focus_index,
+                            },
+                            UiNavigation {
+                                self_id: UiElement::Menu(screen, button_index),
+                                up: ui_element(&navigation.up),
+                                down: ui_element(&navigation.down),
+                                left: ui_element(&navigation.left),
+                                right: ui_element(&navigation.right),
+                            },
+                            AutoNavigation {
+                                wrap: definition.wrap,
+                            },
+                            MaterialNodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    ..style_ui::button_style()
+                                },
+                                material: assets.normal_button_mat.add(NormalButtonMaterial {
+                                    color_texture: Some(assets.ui_assets.button_texture.clone()),
+                                    alpha_mode: AlphaMode::Blend,
+                                }),
+                                ..default()
+                            },
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                MenuButtonLabel,
+                                style_ui::text_bundle(&assets.fonts, text.clone()),
+                            ));
+                            if let Some(detail) = detail {
+                                parent.spawn((
+                                    MenuButtonDetail,
+                                    style_ui::detail_text_bundle(&assets.fonts, detail.clone()),
+                                ));
+                            }
+                        })
+                        .id();
+                    children.push(button);
+                    button_index += 1;
+                }
+                MenuElementDefinition::Label { text } => {
+                    children.push(
+                        commands
+                            .spawn(style_ui::detail_text_bundle(&assets.fonts, text.clone()))
+                            .id(),
+                    );
+                }
+            }
+        }
+
+        commands.entity(menu_entity).push_children(&children);
+        debug!("Built menu {:?} with {} elements", screen, children.len());
+    }
+}
+
+pub fn handle_ui_selection(
+    action_state: Res<ActionState<controls::InputAction>>,
+    mut initial_focus: ResMut<InitialUiFocus>,
+    button_query: Query<(&MenuButton, &UiFocusable)>,
+    mut writers: MenuActionWriters,
+) {
+    if !action_state.just_pressed(&controls::InputAction::Select) {
+        return;
+    }
+
+    let Some((button, _)) = button_query
+        .iter()
+        .find(|(_, focusable)| focusable.is_focused)
+    else {
+        return;
+    };
+
+    initial_focus.menus.insert(button.screen, button.id.clone());
+    match &button.action {
+        MenuAction::AppState(desired_app_state) => {
+            writers
+                .sequence_transition
+                .send(SequenceTransition::to_app_state(*desired_app_state));
+        }
+        MenuAction::OptionsState(desired_options_state) => {
+            writers.edit_options_state.send(EditOptionsState {
+                desired_options_state: *desired_options_state,
+            });
+        }
+        MenuAction::GameState(desired_game_state) => {
+            writers.edit_game_state.send(EditGameState {
+                desired_game_state: *desired_game_state,
+            });
+        }
+        MenuAction::DataState(desired_data_state) => {
+            writers.edit_data_state.send(EditDataState {
+                desired_data_state: *desired_data_state,
+            });
+        }
+        MenuAction::Back => {
+            writers.navigate_back.send(NavigateBack);
+        }
+        MenuAction::Quit => {
+            writers.app_exit.send(AppExit);
+        }
+        MenuAction::Event(name) => {
+            writers.menu_event.send(MenuEvent {
+                screen: button.screen,
+                name: name.clone(),
+            });
+        }
+    }
+}
+
+// custom menu events have no listener by default, log them so new menu files are easy to test
+pub fn log_menu_events(mut read_menu_event: EventReader<MenuEvent>) {
+    for menu_event in read_menu_event.read() {
+        info!(
+            "MenuEvent {:?} sent from {:?}",
+            menu_event.name, menu_event.screen
+        );
+    }
+}
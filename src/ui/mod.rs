@@ -1,7 +1,6 @@
-mod credits;
-mod gameover;
+mod loading;
 mod loadsave;
+pub mod menus;
 mod options;
 mod splash;
-mod title;
 pub mod ui_manager;
@@ -1,9 +1,15 @@
-use super::super::ui_manager::{InitialUiFocus, UiElement, UiFocusable, UiNavigation};
+use super::super::ui_manager::{
+    AutoNavigation, InitialUiFocus, UiElement, UiFocusable, UiNavigation,
+};
 use crate::{
-    config::controls,
+    audio::audio_buses::{AudioBus, EditBusMute, EditBusVolume, VOLUME_STEP},
+    config::{controls, settings::Settings},
     graphics::materials::materials_ui::NormalButtonMaterial,
-    states::options_state::{EditOptionsState, OptionsState},
-    style::style_ui,
+    states::{navigation::NavigateBack, options_state::OptionsState},
+    style::{
+        style_fonts::FontAssets,
+        style_ui::{self, UiAssets},
+    },
 };
 
 use bevy::prelude::*;
@@ -19,7 +25,12 @@ impl Plugin for AudioOptionsUIPlugin {
             .add_systems(OnExit(OptionsState::Audio), despawn_audio_options)
             .add_systems(
                 Update,
-                handle_ui_selection.run_if(in_state(OptionsState::Audio)),
+                (
+                    handle_ui_selection,
+                    handle_volume_slider,
+                    update_volume_sliders.run_if(resource_changed::<Settings>),
+                )
+                    .run_if(in_state(OptionsState::Audio)),
             );
     }
 }
@@ -31,18 +42,26 @@ pub struct AudioOptionsNode;
 
 #[derive(Component, Clone, Copy, Debug, PartialEq)]
 pub enum AudioOptionsElement {
+    Bus(AudioBus),
     Back,
 }
 
+#[derive(Component)]
+pub struct VolumeFill {
+    pub bus: AudioBus,
+}
+
 // systems ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 pub fn spawn_audio_options(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    fonts: Res<FontAssets>,
+    ui_assets: Res<UiAssets>,
+    settings: Res<Settings>,
     initial_focus: Res<InitialUiFocus>,
     mut normal_button_mat: ResMut<Assets<NormalButtonMaterial>>,
 ) {
-    let button_texture_handle: Handle<Image> = asset_server.load(style_ui::SHADER_BUTTON_TEXTURE);
+    let button_texture_handle = ui_assets.button_texture.clone();
 
     let node = commands
         .spawn((
@@ -52,12 +71,17 @@ pub fn spawn_audio_options(
         ))
         .id();
 
-    let mut button = |text: String| {
+    // sliders show the bus volume as a bar under their label
+    let mut button = |text: String, bus: Option<AudioBus>| {
         commands
             .spawn((
                 UiFocusable { is_focused: false },
                 MaterialNodeBundle {
-                    style: style_ui::button_style(),
+                    style: Style {
+                        width: Val::Px(640.0),
+                        flex_direction: FlexDirection::Column,
+                        ..style_ui::button_style()
+                    },
                     material: normal_button_mat.add(NormalButtonMaterial {
                         color_texture: Some(button_texture_handle.clone()),
                         alpha_mode: AlphaMode::Blend,
@@ -66,34 +90,68 @@ pub fn spawn_audio_options(
                 },
             ))
             .with_children(|parent| {
-                parent.spawn(style_ui::text_bundle(&asset_server, text));
+                parent.spawn(style_ui::text_bundle(&fonts, text));
+                if let Some(bus) = bus {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Percent(80.0),
+                                height: Val::Px(12.0),
+                                ..default()
+                            },
+                            background_color: style_ui::NORMAL_BORDER_COLOR.into(),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((
+                                VolumeFill { bus },
+                                NodeBundle {
+                                    style: Style {
+                                        width: Val::Percent(volume_percent(&settings, bus)),
+                                        height: Val::Percent(100.0),
+                                        ..default()
+                                    },
+                                    background_color: style_ui::FOCUSED_BUTTON_COLOR.into(),
+                                    ..default()
+                                },
+                            ));
+                        });
+                }
             })
             .id()
     };
 
     // create the buttons
-    let button_back = button("Back".to_string());
+    let mut elements: Vec<(AudioOptionsElement, Entity)> = Vec::new();
+    for bus in AudioBus::ALL {
+        let slider = button(slider_label(&settings, bus), Some(bus));
+        elements.push((AudioOptionsElement::Bus(bus), slider));
+    }
+    let button_back = button("Back".to_string(), None);
+    elements.push((AudioOptionsElement::Back, button_back));
 
-    // set ui navigation for all elements
-    commands.entity(button_back).insert(UiNavigation {
-        self_id: UiElement::AudioOptions(AudioOptionsElement::Back),
-        up: UiElement::None,
-        down: UiElement::None,
-        left: UiElement::None,
-        right: UiElement::None,
-    });
+    // set ui navigation for all elements, left / right are left free for the sliders
+    for (element, entity) in elements.iter() {
+        commands.entity(*entity).insert((
+            UiNavigation::auto(UiElement::AudioOptions(*element)),
+            AutoNavigation { wrap: true },
+        ));
+    }
 
     // set the initial focused entity when title screen spawns
-    let focus: Entity = match initial_focus.audio_options {
-        AudioOptionsElement::Back => button_back,
-    };
+    let focus: Entity = elements
+        .iter()
+        .find(|(element, _)| *element == initial_focus.audio_options)
+        .map(|(_, entity)| *entity)
+        .unwrap_or(button_back);
     commands
         .entity(focus)
         .remove::<UiFocusable>()
         .insert(UiFocusable { is_focused: true });
 
     // make the buttons children of the parent node
-    commands.entity(node).push_children(&[button_back]);
+    let children: Vec<Entity> = elements.iter().map(|(_, entity)| *entity).collect();
+    commands.entity(node).push_children(&children);
 }
 
 pub fn despawn_audio_options(
@@ -109,18 +167,21 @@ pub fn handle_ui_selection(
     action_state: Res<ActionState<controls::InputAction>>,
     mut initial_focus: ResMut<InitialUiFocus>,
     mut ui_element_query: Query<(&UiNavigation, &mut UiFocusable)>,
-    mut write_edit_options_state: EventWriter<EditOptionsState>,
+    mut write_edit_bus_mute: EventWriter<EditBusMute>,
+    mut write_navigate_back: EventWriter<NavigateBack>,
 ) {
     if action_state.just_pressed(&controls::InputAction::Select) {
         for (ui_navigation, ui_focusable) in &mut ui_element_query {
             if ui_focusable.is_focused {
                 match ui_navigation.self_id {
                     UiElement::AudioOptions(audio_element) => match audio_element {
+                        AudioOptionsElement::Bus(bus) => {
+                            initial_focus.audio_options = audio_element;
+                            write_edit_bus_mute.send(EditBusMute { bus });
+                        }
                         AudioOptionsElement::Back => {
                             initial_focus.audio_options = AudioOptionsElement::Back;
-                            write_edit_options_state.send(EditOptionsState {
-                                desired_options_state: OptionsState::Menu,
-                            });
+                            write_navigate_back.send(NavigateBack);
                         }
                     },
                     _ => {
@@ -136,3 +197,73 @@ pub fn handle_ui_selection(
         }
     }
 }
+
+pub fn handle_volume_slider(
+    action_state: Res<ActionState<controls::InputAction>>,
+    settings: Res<Settings>,
+    ui_element_query: Query<(&UiNavigation, &UiFocusable)>,
+    mut write_edit_bus_volume: EventWriter<EditBusVolume>,
+) {
+    if !action_state.just_pressed(&controls::InputAction::Move) {
+        return;
+    }
+
+    let input_direction = action_state
+        .clamped_axis_pair(&controls::InputAction::Move)
+        .unwrap()
+        .xy();
+    let step = if input_direction.x < -0.85 {
+        -VOLUME_STEP
+    } else if input_direction.x > 0.85 {
+        VOLUME_STEP
+    } else {
+        return;
+    };
+
+    for (ui_navigation, ui_focusable) in ui_element_query.iter() {
+        if let (true, UiElement::AudioOptions(AudioOptionsElement::Bus(bus))) =
+            (ui_focusable.is_focused, ui_navigation.self_id)
+        {
+            // snap to the step so repeated edits don't accumulate float error
+            let volume = settings.audio.bus(bus).volume + step;
+            write_edit_bus_volume.send(EditBusVolume {
+                bus,
+                volume: (volume / VOLUME_STEP).round() * VOLUME_STEP,
+            });
+        }
+    }
+}
+
+pub fn update_volume_sliders(
+    settings: Res<Settings>,
+    slider_query: Query<(&UiNavigation, &Children)>,
+    mut text_query: Query<&mut Text>,
+    mut fill_query: Query<(&VolumeFill, &mut Style)>,
+) {
+    for (ui_navigation, children) in slider_query.iter() {
+        let UiElement::AudioOptions(AudioOptionsElement::Bus(bus)) = ui_navigation.self_id else {
+            continue;
+        };
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = slider_label(&settings, bus);
+        }
+    }
+
+    for (volume_fill, mut fill_style) in fill_query.iter_mut() {
+        fill_style.width = Val::Percent(volume_percent(&settings, volume_fill.bus));
+    }
+}
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+fn volume_percent(settings: &Settings, bus: AudioBus) -> f32 {
+    (settings.audio.bus(bus).volume * 100.0).round()
+}
+
+fn slider_label(settings: &Settings, bus: AudioBus) -> String {
+    if settings.audio.bus(bus).muted {
+        format!("{} Muted", bus.label())
+    } else {
+        format!("{} {}%", bus.label(), volume_percent(settings, bus))
+    }
+}
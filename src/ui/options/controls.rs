@@ -1,13 +1,20 @@
-use super::super::ui_manager::{InitialUiFocus, UiElement, UiFocusable, UiNavigation};
+use super::super::ui_manager::{
+    AutoNavigation, InitialUiFocus, UiElement, UiFocusable, UiNavigation,
+};
 use crate::{
-    config::controls,
+    config::controls::{self, BindingDevice, EditBinding, InputAction, ResetBindings},
     graphics::materials::materials_ui::NormalButtonMaterial,
-    states::options_state::{EditOptionsState, OptionsState},
-    style::style_ui,
+    states::{navigation::NavigateBack, options_state::OptionsState},
+    style::{
+        style_fonts::FontAssets,
+        style_ui::{self, UiAssets},
+    },
 };
 
-use bevy::prelude::*;
-use leafwing_input_manager::action_state::ActionState;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use leafwing_input_manager::{
+    action_state::ActionState, input_map::InputMap, plugin::ToggleActions, user_input::UserInput,
+};
 
 // plugins ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -15,22 +22,64 @@ pub struct ControlsOptionsUIPlugin;
 
 impl Plugin for ControlsOptionsUIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(OptionsState::Controls), spawn_controls_options)
-            .add_systems(OnExit(OptionsState::Controls), despawn_controls_options)
+        app.init_resource::<BindingCapture>()
+            .add_systems(OnEnter(OptionsState::Controls), spawn_controls_options)
+            .add_systems(
+                OnExit(OptionsState::Controls),
+                (despawn_controls_options, reset_binding_capture),
+            )
             .add_systems(
                 Update,
-                handle_ui_selection.run_if(in_state(OptionsState::Controls)),
+                (
+                    handle_ui_selection,
+                    capture_binding,
+                    update_binding_labels.run_if(resource_changed::<InputMap<InputAction>>),
+                    update_controls_message.run_if(resource_changed::<BindingCapture>),
+                )
+                    .run_if(in_state(OptionsState::Controls)),
             );
     }
 }
 
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CapturePhase {
+    #[default]
+    Idle,
+    // wait for the select input to be released so it is not captured as the new binding
+    Releasing(InputAction, BindingDevice),
+    Listening(InputAction, BindingDevice),
+    // wait for the captured input to be released before actions are enabled again
+    Finishing,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct BindingCapture {
+    pub phase: CapturePhase,
+    pub message: String,
+}
+
+// every event a controls options button can send
+#[derive(SystemParam)]
+pub struct ControlsOptionsWriters<'w> {
+    pub edit_binding: EventWriter<'w, EditBinding>,
+    pub reset_bindings: EventWriter<'w, ResetBindings>,
+    pub navigate_back: EventWriter<'w, NavigateBack>,
+}
+
 // components ------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 #[derive(Component)]
 pub struct ControlsOptionsNode;
 
+#[derive(Component)]
+pub struct ControlsMessage;
+
 #[derive(Component, Clone, Copy, Debug, PartialEq)]
 pub enum ControlsOptionsElement {
+    Binding(InputAction, BindingDevice),
+    RestoreDefaults,
     Back,
 }
 
@@ -38,11 +87,13 @@ pub enum ControlsOptionsElement {
 // -----------------------------------------------------------------------------
 pub fn spawn_controls_options(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    fonts: Res<FontAssets>,
+    ui_assets: Res<UiAssets>,
     initial_focus: Res<InitialUiFocus>,
+    input_map: Res<InputMap<InputAction>>,
     mut normal_button_mat: ResMut<Assets<NormalButtonMaterial>>,
 ) {
-    let button_texture_handle: Handle<Image> = asset_server.load(style_ui::SHADER_BUTTON_TEXTURE);
+    let button_texture_handle = ui_assets.button_texture.clone();
 
     let node = commands
         .spawn((
@@ -57,7 +108,10 @@ pub fn spawn_controls_options(
             .spawn((
                 UiFocusable { is_focused: false },
                 MaterialNodeBundle {
-                    style: style_ui::button_style(),
+                    style: Style {
+                        width: Val::Px(520.0),
+                        ..style_ui::button_style()
+                    },
                     material: normal_button_mat.add(NormalButtonMaterial {
                         color_texture: Some(button_texture_handle.clone()),
                         alpha_mode: AlphaMode::Blend,
@@ -66,34 +120,94 @@ pub fn spawn_controls_options(
                 },
             ))
             .with_children(|parent| {
-                parent.spawn(style_ui::text_bundle(&asset_server, text));
+                parent.spawn(style_ui::text_bundle(&fonts, text));
             })
             .id()
     };
 
-    // create the buttons
+    // create the buttons, one keyboard and one gamepad binding per action
+    let mut elements: Vec<(ControlsOptionsElement, Entity)> = Vec::new();
+    let mut rows: Vec<(InputAction, Entity, Entity)> = Vec::new();
+    for action in InputAction::ALL {
+        let mut binding_button = |device: BindingDevice| {
+            let text = controls::binding(&input_map, action, device)
+                .map(controls::binding_label)
+                .unwrap_or_else(|| "Unbound".to_string());
+            let entity = button(text);
+            elements.push((ControlsOptionsElement::Binding(action, device), entity));
+            entity
+        };
+        let button_keyboard = binding_button(BindingDevice::Keyboard);
+        let button_gamepad = binding_button(BindingDevice::Gamepad);
+        rows.push((action, button_keyboard, button_gamepad));
+    }
+    let button_restore_defaults = button("Restore Defaults".to_string());
+    elements.push((
+        ControlsOptionsElement::RestoreDefaults,
+        button_restore_defaults,
+    ));
     let button_back = button("Back".to_string());
+    elements.push((ControlsOptionsElement::Back, button_back));
 
-    // set ui navigation for all elements
-    commands.entity(button_back).insert(UiNavigation {
-        self_id: UiElement::ControlsOptions(ControlsOptionsElement::Back),
-        up: UiElement::None,
-        down: UiElement::None,
-        left: UiElement::None,
-        right: UiElement::None,
-    });
+    // set ui navigation for all elements, the binding grid is resolved from its layout
+    for (element, entity) in elements.iter() {
+        commands.entity(*entity).insert((
+            UiNavigation::auto(UiElement::ControlsOptions(*element)),
+            AutoNavigation { wrap: true },
+        ));
+    }
 
     // set the initial focused entity when title screen spawns
-    let focus: Entity = match initial_focus.controls_options {
-        ControlsOptionsElement::Back => button_back,
-    };
+    let focus: Entity = elements
+        .iter()
+        .find(|(element, _)| *element == initial_focus.controls_options)
+        .map(|(_, entity)| *entity)
+        .unwrap_or(button_back);
     commands
         .entity(focus)
         .remove::<UiFocusable>()
         .insert(UiFocusable { is_focused: true });
 
+    // each action is a row of its name followed by its binding buttons
+    for (action, button_keyboard, button_gamepad) in rows {
+        let label = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(300.0),
+                    justify_content: JustifyContent::End,
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn(style_ui::text_bundle(&fonts, action.label().to_string()));
+            })
+            .id();
+        let row = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            })
+            .push_children(&[label, button_keyboard, button_gamepad])
+            .id();
+        commands.entity(node).add_child(row);
+    }
+
+    let message = commands
+        .spawn((
+            ControlsMessage,
+            style_ui::detail_text_bundle(&fonts, String::new()),
+        ))
+        .id();
+
     // make the buttons children of the parent node
-    commands.entity(node).push_children(&[button_back]);
+    commands
+        .entity(node)
+        .push_children(&[button_restore_defaults, button_back, message]);
 }
 
 pub fn despawn_controls_options(
@@ -105,22 +219,66 @@ pub fn despawn_controls_options(
     }
 }
 
+pub fn reset_binding_capture(
+    mut capture: ResMut<BindingCapture>,
+    mut toggle_actions: ResMut<ToggleActions<InputAction>>,
+) {
+    *capture = BindingCapture::default();
+    toggle_actions.enabled = true;
+}
+
 pub fn handle_ui_selection(
-    action_state: Res<ActionState<controls::InputAction>>,
+    action_state: Res<ActionState<InputAction>>,
+    input_map: Res<InputMap<InputAction>>,
+    mut capture: ResMut<BindingCapture>,
     mut initial_focus: ResMut<InitialUiFocus>,
     mut ui_element_query: Query<(&UiNavigation, &mut UiFocusable)>,
-    mut write_edit_options_state: EventWriter<EditOptionsState>,
+    mut writers: ControlsOptionsWriters,
 ) {
-    if action_state.just_pressed(&controls::InputAction::Select) {
+    if action_state.just_pressed(&InputAction::Select) {
         for (ui_navigation, ui_focusable) in &mut ui_element_query {
             if ui_focusable.is_focused {
                 match ui_navigation.self_id {
                     UiElement::ControlsOptions(controls_element) => match controls_element {
+                        ControlsOptionsElement::Binding(action, device) => {
+                            initial_focus.controls_options = controls_element;
+
+                            // axis actions cycle through presets, everything else is captured
+                            if action.is_axis() {
+                                match next_axis_preset(&input_map, action, device) {
+                                    Some(input) => {
+                                        capture.message = String::new();
+                                        writers.edit_binding.send(EditBinding { action, input });
+                                    }
+                                    None => {
+                                        capture.message = format!(
+                                            "Every {:?} preset is already bound to another action",
+                                            device
+                                        );
+                                    }
+                                }
+                            } else {
+                                capture.phase = CapturePhase::Releasing(action, device);
+                                capture.message = match device {
+                                    BindingDevice::Keyboard => {
+                                        format!("Press a key for {}, Esc to cancel", action.label())
+                                    }
+                                    BindingDevice::Gamepad => format!(
+                                        "Press a gamepad button for {}, Esc to cancel",
+                                        action.label()
+                                    ),
+                                };
+                            }
+                        }
+                        ControlsOptionsElement::RestoreDefaults => {
+                            initial_focus.controls_options =
+                                ControlsOptionsElement::RestoreDefaults;
+                            capture.message = "Default controls restored".to_string();
+                            writers.reset_bindings.send(ResetBindings);
+                        }
                         ControlsOptionsElement::Back => {
                             initial_focus.controls_options = ControlsOptionsElement::Back;
-                            write_edit_options_state.send(EditOptionsState {
-                                desired_options_state: OptionsState::Menu,
-                            });
+                            writers.navigate_back.send(NavigateBack);
                         }
                     },
                     _ => {
@@ -136,3 +294,115 @@ pub fn handle_ui_selection(
         }
     }
 }
+
+pub fn capture_binding(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    input_map: Res<InputMap<InputAction>>,
+    mut capture: ResMut<BindingCapture>,
+    mut toggle_actions: ResMut<ToggleActions<InputAction>>,
+    mut write_edit_binding: EventWriter<EditBinding>,
+) {
+    let is_released =
+        keys.get_pressed().next().is_none() && gamepad_buttons.get_pressed().next().is_none();
+
+    match capture.phase {
+        CapturePhase::Idle => (),
+        CapturePhase::Releasing(action, device) => {
+            // actions are disabled so ui navigation and selection ignore the captured input
+            if toggle_actions.enabled {
+                toggle_actions.enabled = false;
+            }
+            if is_released {
+                capture.phase = CapturePhase::Listening(action, device);
+            }
+        }
+        CapturePhase::Listening(action, device) => {
+            if keys.just_pressed(KeyCode::Escape) {
+                capture.phase = CapturePhase::Finishing;
+                capture.message = "Rebinding cancelled".to_string();
+                return;
+            }
+
+            let captured_input: Option<UserInput> = match device {
+                BindingDevice::Keyboard => keys.get_just_pressed().next().map(|key| (*key).into()),
+                BindingDevice::Gamepad => gamepad_buttons
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| button.button_type.into()),
+            };
+            let Some(input) = captured_input else {
+                return;
+            };
+
+            // refuse the input and keep listening until a free one is pressed
+            if let Some(other_action) = controls::find_conflict(&input_map, action, &input) {
+                capture.message = format!(
+                    "{} is already bound to {}, press another or Esc to cancel",
+                    controls::binding_label(&input),
+                    other_action.label()
+                );
+                return;
+            }
+
+            capture.phase = CapturePhase::Finishing;
+            capture.message = String::new();
+            write_edit_binding.send(EditBinding { action, input });
+        }
+        CapturePhase::Finishing => {
+            if is_released {
+                capture.phase = CapturePhase::Idle;
+                toggle_actions.enabled = true;
+            }
+        }
+    }
+}
+
+pub fn update_binding_labels(
+    input_map: Res<InputMap<InputAction>>,
+    binding_query: Query<(&UiNavigation, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (ui_navigation, children) in binding_query.iter() {
+        let UiElement::ControlsOptions(ControlsOptionsElement::Binding(action, device)) =
+            ui_navigation.self_id
+        else {
+            continue;
+        };
+
+        if let Ok(mut text) = text_query.get_mut(children[0]) {
+            text.sections[0].value = controls::binding(&input_map, action, device)
+                .map(controls::binding_label)
+                .unwrap_or_else(|| "Unbound".to_string());
+        }
+    }
+}
+
+pub fn update_controls_message(
+    capture: Res<BindingCapture>,
+    mut message_query: Query<&mut Text, With<ControlsMessage>>,
+) {
+    for mut text in message_query.iter_mut() {
+        text.sections[0].value = capture.message.clone();
+    }
+}
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// the first preset after the current binding that no other action is using
+fn next_axis_preset(
+    input_map: &InputMap<InputAction>,
+    action: InputAction,
+    device: BindingDevice,
+) -> Option<UserInput> {
+    let presets = controls::axis_presets(action, device);
+    let current_index = controls::binding(input_map, action, device)
+        .and_then(|current| presets.iter().position(|(_, preset)| preset == current));
+    let start_index = current_index.map_or(0, |index| index + 1);
+
+    (0..presets.len())
+        .map(|offset| &presets[(start_index + offset) % presets.len()].1)
+        .filter(|preset| Some(*preset) != controls::binding(input_map, action, device))
+        .find(|preset| controls::find_conflict(input_map, action, preset).is_none())
+        .cloned()
+}
@@ -1,5 +1,4 @@
 pub mod audio;
 pub mod controls;
 pub mod general;
-pub mod menu;
 pub mod video;
@@ -1,48 +1,179 @@
-use super::super::ui_manager::{InitialUiFocus, UiElement, UiFocusable, UiNavigation};
+use super::super::ui_manager::{
+    AutoNavigation, InitialUiFocus, UiElement, UiFocusable, UiNavigation,
+};
 use crate::{
-    config::controls,
+    config::{
+        controls,
+        monitors::{refresh_rate_label, Monitors},
+        settings::Settings,
+        windows::{
+            AspectRatio, DisplayMode, EditCameraFit, EditDisplayMode, EditFramerate, EditMsaa,
+            EditPixelPerfect, EditResolution, EditVsync, FramerateLimiter, FramerateSettings,
+            ResolutionSettings, VideoSettings,
+        },
+    },
     graphics::materials::materials_ui::NormalButtonMaterial,
-    states::options_state::{EditOptionsState, OptionsState},
-    style::style_ui,
+    states::{navigation::NavigateBack, options_state::OptionsState},
+    style::{
+        style_fonts::FontAssets,
+        style_ui::{self, UiAssets},
+    },
 };
 
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use leafwing_input_manager::action_state::ActionState;
 
+pub const CONFIRM_SECONDS: f32 = 15.0;
+
 // plugins ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 pub struct VideoOptionsUIPlugin;
 
 impl Plugin for VideoOptionsUIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(OptionsState::Video), spawn_display_options)
-            .add_systems(OnExit(OptionsState::Video), despawn_display_options)
+        app.add_event::<ApplyVideoSettings>()
+            .add_event::<ConfirmVideoSettings>()
+            .add_systems(OnEnter(OptionsState::Video), spawn_display_options)
+            .add_systems(
+                OnExit(OptionsState::Video),
+                (despawn_display_options, revert_unconfirmed_video_settings),
+            )
             .add_systems(
                 Update,
-                handle_ui_selection.run_if(in_state(OptionsState::Video)),
-            );
+                (
+                    handle_ui_selection,
+                    handle_picker_input,
+                    handle_apply_video_settings,
+                    tick_video_confirmation,
+                    sync_confirmation_panel,
+                    update_video_labels,
+                )
+                    .run_if(in_state(OptionsState::Video)),
+            )
+            // also runs on the frame the screen is left, see revert_unconfirmed_video_settings
+            .add_systems(Update, handle_confirm_video_settings);
     }
 }
 
+// resources -------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// monitor, resolution and display mode are staged here until Apply is selected
+#[derive(Resource, Debug)]
+pub struct PendingVideoSettings {
+    pub monitor: usize,
+    pub resolution: Vec2,
+    pub display_mode: DisplayMode,
+    pub refresh_rate_millihertz: Option<u32>,
+}
+
+impl PendingVideoSettings {
+    pub fn from_settings(video: &VideoSettings) -> PendingVideoSettings {
+        PendingVideoSettings {
+            monitor: video.monitor,
+            resolution: video.resolution,
+            display_mode: video.display_mode,
+            refresh_rate_millihertz: video.refresh_rate_millihertz,
+        }
+    }
+}
+
+// present while the "Keep these settings?" countdown runs
+#[derive(Resource, Debug)]
+pub struct VideoConfirmation {
+    pub timer: Timer,
+    pub previous: VideoSettings,
+}
+
+// every event a video options button can send
+#[derive(SystemParam)]
+pub struct VideoOptionsWriters<'w> {
+    pub edit_vsync: EventWriter<'w, EditVsync>,
+    pub edit_msaa: EventWriter<'w, EditMsaa>,
+    pub edit_framerate: EventWriter<'w, EditFramerate>,
+    pub edit_camera_fit: EventWriter<'w, EditCameraFit>,
+    pub edit_pixel_perfect: EventWriter<'w, EditPixelPerfect>,
+    pub apply_video_settings: EventWriter<'w, ApplyVideoSettings>,
+    pub confirm_video_settings: EventWriter<'w, ConfirmVideoSettings>,
+    pub navigate_back: EventWriter<'w, NavigateBack>,
+}
+
+// what the monitor, resolution and refresh rate pickers can choose from
+#[derive(SystemParam)]
+pub struct VideoModeOptions<'w> {
+    pub monitors: Res<'w, Monitors>,
+    pub resolution_settings: Res<'w, ResolutionSettings>,
+}
+
+// what spawn_button reads to build a video options button
+#[derive(SystemParam)]
+pub struct VideoButtonAssets<'w> {
+    pub fonts: Res<'w, FontAssets>,
+    pub ui_assets: Res<'w, UiAssets>,
+    pub normal_button_mat: ResMut<'w, Assets<NormalButtonMaterial>>,
+}
+
+// the display changes applying or reverting the video settings can send
+#[derive(SystemParam)]
+pub struct DisplayWriters<'w> {
+    pub edit_resolution: EventWriter<'w, EditResolution>,
+    pub edit_display_mode: EventWriter<'w, EditDisplayMode>,
+}
+
+// button details and the countdown text update_video_labels writes to
+#[derive(SystemParam)]
+pub struct VideoLabelTexts<'w, 's> {
+    pub detail: Query<'w, 's, &'static mut Text, Without<VideoConfirmText>>,
+    pub confirm: Query<'w, 's, &'static mut Text, With<VideoConfirmText>>,
+}
+
 // components ------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 #[derive(Component)]
 pub struct VideoOptionsNode;
 
+#[derive(Component)]
+pub struct VideoConfirmNode;
+
+#[derive(Component)]
+pub struct VideoConfirmText;
+
 #[derive(Component, Clone, Copy, Debug, PartialEq)]
 pub enum VideoOptionsElement {
+    Monitor,
+    Resolution,
+    DisplayMode,
+    RefreshRate,
+    Vsync,
+    Msaa,
+    Framerate,
+    CameraFit,
+    PixelPerfect,
+    Apply,
     Back,
+    Keep,
+    Revert,
+}
+
+// events ----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[derive(Event)]
+pub struct ApplyVideoSettings;
+
+#[derive(Event)]
+pub struct ConfirmVideoSettings {
+    pub keep: bool,
 }
 
 // systems ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 pub fn spawn_display_options(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    mut button_assets: VideoButtonAssets,
+    settings: Res<Settings>,
     initial_focus: Res<InitialUiFocus>,
-    mut normal_button_mat: ResMut<Assets<NormalButtonMaterial>>,
 ) {
-    let button_texture_handle: Handle<Image> = asset_server.load(style_ui::SHADER_BUTTON_TEXTURE);
+    // start from the applied settings every time the screen opens
+    commands.insert_resource(PendingVideoSettings::from_settings(&settings.video));
 
     let node = commands
         .spawn((
@@ -52,48 +183,104 @@ pub fn spawn_display_options(
         ))
         .id();
 
-    let mut button = |text: String| {
-        commands
-            .spawn((
-                UiFocusable { is_focused: false },
-                MaterialNodeBundle {
-                    style: style_ui::button_style(),
-                    material: normal_button_mat.add(NormalButtonMaterial {
-                        color_texture: Some(button_texture_handle.clone()),
-                        alpha_mode: AlphaMode::Blend,
-                    }),
+    // display options on the left, applied together, the rest on the right apply immediately
+    let columns: [&[VideoOptionsElement]; 2] = [
+        &[
+            VideoOptionsElement::Monitor,
+            VideoOptionsElement::Resolution,
+            VideoOptionsElement::DisplayMode,
+            VideoOptionsElement::RefreshRate,
+            VideoOptionsElement::Apply,
+        ],
+        &[
+            VideoOptionsElement::Vsync,
+            VideoOptionsElement::Msaa,
+            VideoOptionsElement::Framerate,
+            VideoOptionsElement::CameraFit,
+            VideoOptionsElement::PixelPerfect,
+            VideoOptionsElement::Back,
+        ],
+    ];
+
+    // create the buttons, values are filled in by update_video_labels
+    let mut elements: Vec<(VideoOptionsElement, Entity)> = Vec::new();
+    let row = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    for column_elements in columns {
+        let column = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
                     ..default()
                 },
-            ))
-            .with_children(|parent| {
-                parent.spawn(style_ui::text_bundle(&asset_server, text));
+                ..default()
             })
-            .id()
-    };
-
-    // create the buttons
-    let button_back = button("Back".to_string());
+            .id();
+        for &element in column_elements {
+            let entity = spawn_button(&mut commands, &mut button_assets, element);
+            commands.entity(column).add_child(entity);
+            elements.push((element, entity));
+        }
+        commands.entity(row).add_child(column);
+    }
 
     // set ui navigation for all elements
-    commands.entity(button_back).insert(UiNavigation {
-        self_id: UiElement::VideoOptions(VideoOptionsElement::Back),
-        up: UiElement::None,
-        down: UiElement::None,
-        left: UiElement::None,
-        right: UiElement::None,
-    });
+    //  left / right on the pickers change their value instead of the focus
+    let picker_navigation = |element: VideoOptionsElement| {
+        let (up, down) = match element {
+            VideoOptionsElement::Monitor => {
+                (VideoOptionsElement::Apply, VideoOptionsElement::Resolution)
+            }
+            VideoOptionsElement::Resolution => (
+                VideoOptionsElement::Monitor,
+                VideoOptionsElement::DisplayMode,
+            ),
+            VideoOptionsElement::RefreshRate => {
+                (VideoOptionsElement::DisplayMode, VideoOptionsElement::Apply)
+            }
+            _ => return None,
+        };
+        Some(UiNavigation {
+            self_id: UiElement::VideoOptions(element),
+            up: UiElement::VideoOptions(up),
+            down: UiElement::VideoOptions(down),
+            left: UiElement::None,
+            right: UiElement::None,
+        })
+    };
+    for (element, entity) in elements.iter() {
+        match picker_navigation(*element) {
+            Some(ui_navigation) => {
+                commands.entity(*entity).insert(ui_navigation);
+            }
+            None => {
+                commands.entity(*entity).insert((
+                    UiNavigation::auto(UiElement::VideoOptions(*element)),
+                    AutoNavigation { wrap: true },
+                ));
+            }
+        }
+    }
 
     // set the initial focused entity when title screen spawns
-    let focus: Entity = match initial_focus.video_options {
-        VideoOptionsElement::Back => button_back,
-    };
+    let focus: Entity = elements
+        .iter()
+        .find(|(element, _)| *element == initial_focus.video_options)
+        .map(|(_, entity)| *entity)
+        .unwrap_or(elements[0].1);
     commands
         .entity(focus)
         .remove::<UiFocusable>()
         .insert(UiFocusable { is_focused: true });
 
-    // make the buttons children of the parent node
-    commands.entity(node).push_children(&[button_back]);
+    commands.entity(node).add_child(row);
 }
 
 pub fn despawn_display_options(
@@ -103,26 +290,88 @@ pub fn despawn_display_options(
     for display_entity in menu_query.iter() {
         commands.entity(display_entity).despawn_recursive();
     }
+    commands.remove_resource::<PendingVideoSettings>();
+}
+
+// leaving the screen during the countdown is treated as not confirming
+pub fn revert_unconfirmed_video_settings(
+    confirmation: Option<Res<VideoConfirmation>>,
+    mut write_confirm_video_settings: EventWriter<ConfirmVideoSettings>,
+) {
+    if confirmation.is_some() {
+        write_confirm_video_settings.send(ConfirmVideoSettings { keep: false });
+    }
 }
 
 pub fn handle_ui_selection(
     action_state: Res<ActionState<controls::InputAction>>,
+    video_modes: VideoModeOptions,
+    mut pending: ResMut<PendingVideoSettings>,
     mut initial_focus: ResMut<InitialUiFocus>,
     mut ui_element_query: Query<(&UiNavigation, &mut UiFocusable)>,
-    mut write_edit_options_state: EventWriter<EditOptionsState>,
+    mut writers: VideoOptionsWriters,
 ) {
     if action_state.just_pressed(&controls::InputAction::Select) {
         for (ui_navigation, ui_focusable) in &mut ui_element_query {
             if ui_focusable.is_focused {
                 match ui_navigation.self_id {
-                    UiElement::VideoOptions(video_element) => match video_element {
-                        VideoOptionsElement::Back => {
-                            initial_focus.video_options = VideoOptionsElement::Back;
-                            write_edit_options_state.send(EditOptionsState {
-                                desired_options_state: OptionsState::Menu,
-                            });
+                    UiElement::VideoOptions(video_element) => {
+                        // the confirmation buttons only exist while the countdown runs
+                        if !matches!(
+                            video_element,
+                            VideoOptionsElement::Keep | VideoOptionsElement::Revert
+                        ) {
+                            initial_focus.video_options = video_element;
                         }
-                    },
+
+                        match video_element {
+                            VideoOptionsElement::Monitor
+                            | VideoOptionsElement::Resolution
+                            | VideoOptionsElement::RefreshRate => {
+                                step_picker(
+                                    &mut pending,
+                                    video_element,
+                                    1,
+                                    &video_modes.monitors,
+                                    &video_modes.resolution_settings,
+                                );
+                            }
+                            VideoOptionsElement::DisplayMode => {
+                                pending.display_mode = pending.display_mode.next();
+                            }
+                            VideoOptionsElement::Vsync => {
+                                writers.edit_vsync.send(EditVsync);
+                            }
+                            VideoOptionsElement::Msaa => {
+                                writers.edit_msaa.send(EditMsaa);
+                            }
+                            VideoOptionsElement::Framerate => {
+                                writers.edit_framerate.send(EditFramerate);
+                            }
+                            VideoOptionsElement::CameraFit => {
+                                writers.edit_camera_fit.send(EditCameraFit);
+                            }
+                            VideoOptionsElement::PixelPerfect => {
+                                writers.edit_pixel_perfect.send(EditPixelPerfect);
+                            }
+                            VideoOptionsElement::Apply => {
+                                writers.apply_video_settings.send(ApplyVideoSettings);
+                            }
+                            VideoOptionsElement::Keep => {
+                                writers
+                                    .confirm_video_settings
+                                    .send(ConfirmVideoSettings { keep: true });
+                            }
+                            VideoOptionsElement::Revert => {
+                                writers
+                                    .confirm_video_settings
+                                    .send(ConfirmVideoSettings { keep: false });
+                            }
+                            VideoOptionsElement::Back => {
+                                writers.navigate_back.send(NavigateBack);
+                            }
+                        }
+                    }
                     _ => {
                         error!(
                             "UiElement {:?} encountered non-UiElement::VideoOptions entity on video options screen",
@@ -136,3 +385,444 @@ pub fn handle_ui_selection(
         }
     }
 }
+
+pub fn handle_picker_input(
+    action_state: Res<ActionState<controls::InputAction>>,
+    video_modes: VideoModeOptions,
+    mut pending: ResMut<PendingVideoSettings>,
+    ui_element_query: Query<(&UiNavigation, &UiFocusable)>,
+) {
+    if !action_state.just_pressed(&controls::InputAction::Move) {
+        return;
+    }
+
+    let input_direction = action_state
+        .clamped_axis_pair(&controls::InputAction::Move)
+        .unwrap()
+        .xy();
+    let step = if input_direction.x < -0.85 {
+        -1
+    } else if input_direction.x > 0.85 {
+        1
+    } else {
+        return;
+    };
+
+    for (ui_navigation, ui_focusable) in ui_element_query.iter() {
+        if let (true, UiElement::VideoOptions(video_element)) =
+            (ui_focusable.is_focused, ui_navigation.self_id)
+        {
+            step_picker(
+                &mut pending,
+                video_element,
+                step,
+                &video_modes.monitors,
+                &video_modes.resolution_settings,
+            );
+        }
+    }
+}
+
+pub fn handle_apply_video_settings(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    video_modes: VideoModeOptions,
+    pending: Res<PendingVideoSettings>,
+    confirmation: Option<ResMut<VideoConfirmation>>,
+    mut read_apply_video_settings: EventReader<ApplyVideoSettings>,
+    mut display_writers: DisplayWriters,
+) {
+    if read_apply_video_settings.read().count() == 0 {
+        return;
+    }
+
+    let video = &settings.video;
+    let is_resolution_changed = pending.resolution != video.resolution;
+    let is_display_changed = pending.display_mode != video.display_mode
+        || pending.monitor != video.monitor
+        || pending.refresh_rate_millihertz != video.refresh_rate_millihertz;
+
+    if is_resolution_changed {
+        let aspect_ratio = video_modes
+            .monitors
+            .resolutions(pending.monitor, &video_modes.resolution_settings)
+            .into_iter()
+            .find(|(resolution, _)| *resolution == pending.resolution)
+            .map_or(AspectRatio::nearest(pending.resolution), |(_, aspect)| {
+                aspect
+            });
+        display_writers.edit_resolution.send(EditResolution {
+            resolution: pending.resolution,
+            aspect_ratio,
+        });
+    }
+    if is_display_changed {
+        display_writers.edit_display_mode.send(EditDisplayMode {
+            display_mode: pending.display_mode,
+            monitor: pending.monitor,
+            refresh_rate_millihertz: pending.refresh_rate_millihertz,
+        });
+    }
+
+    // the previous values are kept so an unreadable display can revert on its own
+    //  applying again during the countdown only restarts it, previous stays the confirmed settings
+    if is_resolution_changed || is_display_changed {
+        match confirmation {
+            Some(mut confirmation) => confirmation.timer.reset(),
+            None => commands.insert_resource(VideoConfirmation {
+                timer: Timer::from_seconds(CONFIRM_SECONDS, TimerMode::Once),
+                previous: video.clone(),
+            }),
+        }
+    }
+}
+
+// real time so the countdown still runs when options are opened over a paused game
+pub fn tick_video_confirmation(
+    time: Res<Time<Real>>,
+    confirmation: Option<ResMut<VideoConfirmation>>,
+    mut write_confirm_video_settings: EventWriter<ConfirmVideoSettings>,
+) {
+    let Some(mut confirmation) = confirmation else {
+        return;
+    };
+
+    if confirmation.timer.tick(time.delta()).just_finished() {
+        info!("Video settings were not confirmed, reverting.");
+        write_confirm_video_settings.send(ConfirmVideoSettings { keep: false });
+    }
+}
+
+pub fn handle_confirm_video_settings(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    confirmation: Option<Res<VideoConfirmation>>,
+    pending: Option<ResMut<PendingVideoSettings>>,
+    mut read_confirm_video_settings: EventReader<ConfirmVideoSettings>,
+    mut display_writers: DisplayWriters,
+) {
+    let Some(is_kept) = read_confirm_video_settings
+        .read()
+        .last()
+        .map(|confirm| confirm.keep)
+    else {
+        return;
+    };
+    let Some(confirmation) = confirmation else {
+        return;
+    };
+    commands.remove_resource::<VideoConfirmation>();
+
+    if is_kept {
+        info!("Video settings kept.");
+        return;
+    }
+
+    let (previous, video) = (&confirmation.previous, &settings.video);
+    if previous.resolution != video.resolution {
+        display_writers.edit_resolution.send(EditResolution {
+            resolution: previous.resolution,
+            aspect_ratio: previous.aspect_ratio,
+        });
+    }
+    if previous.display_mode != video.display_mode
+        || previous.monitor != video.monitor
+        || previous.refresh_rate_millihertz != video.refresh_rate_millihertz
+    {
+        display_writers.edit_display_mode.send(EditDisplayMode {
+            display_mode: previous.display_mode,
+            monitor: previous.monitor,
+            refresh_rate_millihertz: previous.refresh_rate_millihertz,
+        });
+    }
+
+    // the pickers show the reverted values again
+    if let Some(mut pending) = pending {
+        *pending = PendingVideoSettings::from_settings(previous);
+    }
+}
+
+pub fn sync_confirmation_panel(
+    mut commands: Commands,
+    mut button_assets: VideoButtonAssets,
+    confirmation: Option<Res<VideoConfirmation>>,
+    node_query: Query<Entity, With<VideoOptionsNode>>,
+    panel_query: Query<Entity, With<VideoConfirmNode>>,
+    mut ui_element_query: Query<(&UiNavigation, &mut UiFocusable)>,
+) {
+    let panel = panel_query.get_single().ok();
+
+    match (confirmation.is_some(), panel) {
+        (true, None) => {
+            let Ok(node) = node_query.get_single() else {
+                return;
+            };
+
+            // focus moves to Keep so confirming is a single press
+            for (_, mut ui_focusable) in &mut ui_element_query {
+                if ui_focusable.is_focused {
+                    ui_focusable.is_focused = false;
+                }
+            }
+
+            let button_keep =
+                spawn_button(&mut commands, &mut button_assets, VideoOptionsElement::Keep);
+            let button_revert = spawn_button(
+                &mut commands,
+                &mut button_assets,
+                VideoOptionsElement::Revert,
+            );
+            for (entity, element) in [
+                (button_keep, VideoOptionsElement::Keep),
+                (button_revert, VideoOptionsElement::Revert),
+            ] {
+                commands.entity(entity).insert((
+                    UiNavigation::auto(UiElement::VideoOptions(element)),
+                    AutoNavigation { wrap: true },
+                ));
+            }
+            commands
+                .entity(button_keep)
+                .remove::<UiFocusable>()
+                .insert(UiFocusable { is_focused: true });
+
+            let text = commands
+                .spawn((
+                    VideoConfirmText,
+                    style_ui::detail_text_bundle(&button_assets.fonts, String::new()),
+                ))
+                .id();
+            let panel = commands
+                .spawn((
+                    VideoConfirmNode,
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ))
+                .push_children(&[text, button_keep, button_revert])
+                .id();
+            commands.entity(node).add_child(panel);
+        }
+        (false, Some(panel)) => {
+            commands.entity(panel).despawn_recursive();
+
+            // hand focus back to Apply, the confirmation buttons are gone
+            for (ui_navigation, mut ui_focusable) in &mut ui_element_query {
+                let is_apply =
+                    ui_navigation.self_id == UiElement::VideoOptions(VideoOptionsElement::Apply);
+                if ui_focusable.is_focused != is_apply {
+                    ui_focusable.is_focused = is_apply;
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+pub fn update_video_labels(
+    settings: Res<Settings>,
+    monitors: Res<Monitors>,
+    framerate_settings: Res<FramerateSettings>,
+    pending: Res<PendingVideoSettings>,
+    confirmation: Option<Res<VideoConfirmation>>,
+    button_query: Query<(&UiNavigation, &Children)>,
+    mut texts: VideoLabelTexts,
+) {
+    if let Some(confirmation) = confirmation {
+        let seconds_left = confirmation.timer.remaining_secs().ceil();
+        for mut text in texts.confirm.iter_mut() {
+            text.sections[0].value = format!("Keep these settings? {}", seconds_left);
+        }
+    }
+
+    if !settings.is_changed() && !pending.is_changed() && !monitors.is_changed() {
+        return;
+    }
+
+    let video = &settings.video;
+    let pending_marker = |is_pending: bool| if is_pending { " (not applied)" } else { "" };
+    let on_off = |is_on: bool| if is_on { "On" } else { "Off" };
+
+    for (ui_navigation, children) in button_query.iter() {
+        let UiElement::VideoOptions(video_element) = ui_navigation.self_id else {
+            continue;
+        };
+        let detail = match video_element {
+            VideoOptionsElement::Monitor => format!(
+                "< {} >{}",
+                monitors.label(pending.monitor),
+                pending_marker(pending.monitor != video.monitor)
+            ),
+            VideoOptionsElement::Resolution => format!(
+                "< {} x {} >{}",
+                pending.resolution.x,
+                pending.resolution.y,
+                pending_marker(pending.resolution != video.resolution)
+            ),
+            VideoOptionsElement::DisplayMode => format!(
+                "{}{}",
+                pending.display_mode.label(),
+                pending_marker(pending.display_mode != video.display_mode)
+            ),
+            VideoOptionsElement::RefreshRate => match pending.display_mode {
+                DisplayMode::ExclusiveFullscreen => format!(
+                    "< {} >{}",
+                    refresh_rate_label(pending.refresh_rate_millihertz),
+                    pending_marker(
+                        pending.refresh_rate_millihertz != video.refresh_rate_millihertz
+                    )
+                ),
+                _ => "Exclusive only".to_string(),
+            },
+            VideoOptionsElement::Vsync => on_off(video.vsync).to_string(),
+            VideoOptionsElement::Msaa => match video.msaa_samples {
+                1 => "Off".to_string(),
+                samples => format!("{}x", samples),
+            },
+            VideoOptionsElement::Framerate => match video.vsync {
+                true => "Locked by VSync".to_string(),
+                false => match video.framerate_limiter {
+                    FramerateLimiter::Auto => "Auto".to_string(),
+                    FramerateLimiter::Manual => {
+                        format!("{} fps", framerate_settings.manual_fps)
+                    }
+                    FramerateLimiter::Off => "Off".to_string(),
+                },
+            },
+            VideoOptionsElement::CameraFit => match video.pixel_perfect.is_enabled() {
+                true => "Locked by Pixel Perfect".to_string(),
+                false => video.camera_fit.label().to_string(),
+            },
+            VideoOptionsElement::PixelPerfect => video.pixel_perfect.label().to_string(),
+            _ => continue,
+        };
+
+        if let Some(mut text) = children
+            .get(1)
+            .and_then(|child| texts.detail.get_mut(*child).ok())
+        {
+            text.sections[0].value = detail;
+        }
+    }
+}
+
+// helpers ---------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// moves a picker to the next / previous option, values missing from the list start over
+fn step_picker(
+    pending: &mut PendingVideoSettings,
+    element: VideoOptionsElement,
+    step: isize,
+    monitors: &Monitors,
+    resolution_settings: &ResolutionSettings,
+) {
+    fn cycle<T: PartialEq + Copy>(options: &[T], current: T, step: isize) -> T {
+        if options.is_empty() {
+            return current;
+        }
+        let index = options.iter().position(|option| *option == current);
+        let next = match index {
+            Some(index) => (index as isize + step).rem_euclid(options.len() as isize),
+            None => 0,
+        };
+        options[next as usize]
+    }
+
+    match element {
+        VideoOptionsElement::Monitor => {
+            let options: Vec<usize> = (0..monitors.count()).collect();
+            pending.monitor = cycle(&options, pending.monitor, step);
+        }
+        VideoOptionsElement::Resolution => {
+            let options: Vec<Vec2> = monitors
+                .resolutions(pending.monitor, resolution_settings)
+                .into_iter()
+                .map(|(resolution, _)| resolution)
+                .collect();
+            pending.resolution = cycle(&options, pending.resolution, step);
+        }
+        VideoOptionsElement::RefreshRate => {
+            if pending.display_mode != DisplayMode::ExclusiveFullscreen {
+                return;
+            }
+            let options: Vec<Option<u32>> = std::iter::once(None)
+                .chain(
+                    monitors
+                        .refresh_rates(pending.monitor, pending.resolution)
+                        .into_iter()
+                        .map(Some),
+                )
+                .collect();
+            pending.refresh_rate_millihertz =
+                cycle(&options, pending.refresh_rate_millihertz, step);
+            return;
+        }
+        _ => return,
+    }
+
+    // a refresh rate the new monitor or resolution lacks falls back to the highest
+    if pending.refresh_rate_millihertz.is_some_and(|rate| {
+        !monitors
+            .refresh_rates(pending.monitor, pending.resolution)
+            .contains(&rate)
+    }) {
+        pending.refresh_rate_millihertz = None;
+    }
+}
+
+fn spawn_button(
+    commands: &mut Commands,
+    button_assets: &mut VideoButtonAssets,
+    element: VideoOptionsElement,
+) -> Entity {
+    let (text, has_detail) = match element {
+        VideoOptionsElement::Monitor => ("Monitor", true),
+        VideoOptionsElement::Resolution => ("Resolution", true),
+        VideoOptionsElement::DisplayMode => ("Display Mode", true),
+        VideoOptionsElement::RefreshRate => ("Refresh Rate", true),
+        VideoOptionsElement::Vsync => ("VSync", true),
+        VideoOptionsElement::Msaa => ("MSAA", true),
+        VideoOptionsElement::Framerate => ("Framerate", true),
+        VideoOptionsElement::CameraFit => ("Camera Fit", true),
+        VideoOptionsElement::PixelPerfect => ("Pixel Perfect", true),
+        VideoOptionsElement::Apply => ("Apply", false),
+        VideoOptionsElement::Back => ("Back", false),
+        VideoOptionsElement::Keep => ("Keep", false),
+        VideoOptionsElement::Revert => ("Revert", false),
+    };
+
+    commands
+        .spawn((
+            UiFocusable { is_focused: false },
+            MaterialNodeBundle {
+                style: Style {
+                    width: Val::Px(640.0),
+                    flex_direction: FlexDirection::Column,
+                    ..style_ui::button_style()
+                },
+                material: button_assets.normal_button_mat.add(NormalButtonMaterial {
+                    color_texture: Some(button_assets.ui_assets.button_texture.clone()),
+                    alpha_mode: AlphaMode::Blend,
+                }),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(style_ui::text_bundle(
+                &button_assets.fonts,
+                text.to_string(),
+            ));
+            if has_detail {
+                parent.spawn(style_ui::detail_text_bundle(
+                    &button_assets.fonts,
+                    String::new(),
+                ));
+            }
+        })
+        .id()
+}
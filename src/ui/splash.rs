@@ -1,5 +1,8 @@
 use crate::{
-    states::app_state::{AppState, EditAppState},
+    states::{
+        app_state::{AppState, EditAppState},
+        data_state::DataState,
+    },
     style::style_splash,
 };
 
@@ -66,6 +69,7 @@ pub fn despawn_splash(mut commands: Commands, splash_query: Query<Entity, With<S
 pub fn splash_animations(
     time: Res<Time>,
     asset_server: Res<AssetServer>,
+    data_state: Res<State<DataState>>,
     mut node_query: Query<(&mut SplashNode, &mut Visibility, &Children)>,
     mut logo_query: Query<&mut UiImage>,
     mut write_edit_app_state: EventWriter<EditAppState>,
@@ -80,11 +84,13 @@ pub fn splash_animations(
     // 2.5 : hide studio logo
     // 3.5 : display engine logo
     // 5.0 : hide engine logo
-    // 6.0 : end splash screen
+    // 6.0 : end splash screen (once boot assets have finished loading)
     if splash_node.splash_timer.elapsed_secs() >= 6.0 {
-        write_edit_app_state.send(EditAppState {
-            desired_app_state: AppState::Title,
-        });
+        if *data_state.get() == DataState::Idle {
+            write_edit_app_state.send(EditAppState {
+                desired_app_state: AppState::Title,
+            });
+        }
     } else if splash_node.splash_timer.elapsed_secs() >= 3.5 {
         if !splash_node.engine_logo_timer.finished() {
             *node_visibility = Visibility::Visible;
@@ -1,20 +1,25 @@
 use super::{
-    credits::*,
-    gameover::*,
+    loading::*,
     loadsave::*,
-    options::{audio::*, controls::*, general::*, menu::*, video::*},
+    menus::*,
+    options::{audio::*, controls::*, general::*, video::*},
     splash::*,
-    title::*,
 };
 use crate::{
     config::controls,
     graphics::materials::materials_ui::{FocusedButtonMaterial, NormalButtonMaterial},
-    states::app_state::AppState,
-    style::{style_fonts, style_ui},
+    states::{app_state::AppState, game_state::GameState},
+    style::{
+        style_fonts::FontAssets,
+        style_ui::{self, UiAssets},
+    },
 };
 
-use bevy::prelude::*;
-use leafwing_input_manager::action_state::ActionState;
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+use leafwing_input_manager::{
+    action_state::ActionState,
+    plugin::{InputManagerSystem, ToggleActions},
+};
 
 // plugins ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
@@ -24,24 +29,33 @@ impl Plugin for UiManagerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<InitialUiFocus>()
             .add_plugins((
-                OptionsMenuUIPlugin,
+                MenuScreensUIPlugin,
                 AudioOptionsUIPlugin,
                 ControlsOptionsUIPlugin,
-                VideoOptionsUIPlugin,
                 GeneralOptionsUIPlugin,
+                VideoOptionsUIPlugin,
                 SplashScreenUIPlugin,
-                TitleScreenUIPlugin,
                 LoadSaveScreenUIPlugin,
-                CreditsScreenUIPlugin,
-                GameoverScreenUIPlugin,
+                LoadingScreenUIPlugin,
             ))
             .add_systems(
                 Update,
                 (
-                    animate_focused_material.run_if(not(in_state(AppState::Game))),
-                    style_ui_element.run_if(not(in_state(AppState::Game))),
-                    handle_ui_navigation.run_if(not(in_state(AppState::Game))),
+                    animate_focused_material.run_if(in_menu),
+                    // boot assets used for styling are still loading during the splash screen
+                    style_ui_element
+                        .run_if(in_menu)
+                        .run_if(not(in_state(AppState::Splash))),
+                    handle_ui_navigation.run_if(in_menu),
+                    add_pointer_interaction.run_if(in_menu),
                 ),
+            )
+            .add_systems(
+                PreUpdate,
+                // pressing Select here lets every screen's handle_ui_selection see the click
+                handle_ui_pointer
+                    .in_set(InputManagerSystem::ManualControl)
+                    .run_if(in_menu),
             );
     }
 }
@@ -50,12 +64,8 @@ impl Plugin for UiManagerPlugin {
 // -----------------------------------------------------------------------------
 #[derive(Resource, Debug)]
 pub struct InitialUiFocus {
-    pub title: TitleElement,
-    pub loadsave: LoadSaveElement,
-    pub credits: CreditsElement,
-    pub gameover: GameoverElement,
-    pub options_menu: OptionsMenuElement,
-    pub general_options: GeneralOptionsElement,
+    // data driven menus remember the id of the last selected button
+    pub menus: HashMap<MenuScreen, String>,
     pub controls_options: ControlsOptionsElement,
     pub audio_options: AudioOptionsElement,
     pub video_options: VideoOptionsElement,
@@ -64,12 +74,7 @@ pub struct InitialUiFocus {
 impl Default for InitialUiFocus {
     fn default() -> InitialUiFocus {
         InitialUiFocus {
-            title: TitleElement::Play,
-            loadsave: LoadSaveElement::Profile1,
-            credits: CreditsElement::Back,
-            gameover: GameoverElement::Game,
-            options_menu: OptionsMenuElement::General,
-            general_options: GeneralOptionsElement::Back,
+            menus: HashMap::new(),
             controls_options: ControlsOptionsElement::Back,
             audio_options: AudioOptionsElement::Back,
             video_options: VideoOptionsElement::Back,
@@ -77,6 +82,13 @@ impl Default for InitialUiFocus {
     }
 }
 
+// both button materials style_ui_element swaps between
+#[derive(SystemParam)]
+pub struct ButtonMaterials<'w> {
+    pub normal: ResMut<'w, Assets<NormalButtonMaterial>>,
+    pub focused: ResMut<'w, Assets<FocusedButtonMaterial>>,
+}
+
 // components ------------------------------------------------------------------
 // -----------------------------------------------------------------------------
 #[derive(Component)]
@@ -87,12 +99,7 @@ pub struct UiFocusable {
 #[derive(Component, Clone, Copy, Debug, PartialEq)]
 pub enum UiElement {
     None,
-    Title(TitleElement),
-    LoadSave(LoadSaveElement),
-    Gameover(GameoverElement),
-    Credits(CreditsElement),
-    OptionsMenu(OptionsMenuElement),
-    GeneralOptions(GeneralOptionsElement),
+    Menu(MenuScreen, usize),
     ControlsOptions(ControlsOptionsElement),
     AudioOptions(AudioOptionsElement),
     VideoOptions(VideoOptionsElement),
@@ -107,10 +114,38 @@ pub struct UiNavigation {
     pub right: UiElement,
 }
 
+impl UiNavigation {
+    // no explicit links, every direction is resolved by AutoNavigation
+    pub fn auto(self_id: UiElement) -> UiNavigation {
+        UiNavigation {
+            self_id,
+            up: UiElement::None,
+            down: UiElement::None,
+            left: UiElement::None,
+            right: UiElement::None,
+        }
+    }
+}
+
+// opt-in: directions left as UiElement::None in UiNavigation are resolved from the
+//  laid-out position of the other focusables, explicit links still take priority
+#[derive(Component, Clone, Copy, Debug)]
+pub struct AutoNavigation {
+    pub wrap: bool,
+}
+
+// run conditions --------------------------------------------------------------
+// -----------------------------------------------------------------------------
+// menus take input everywhere except during gameplay, the pause menu is drawn over the game
+pub fn in_menu(app_state: Res<State<AppState>>, game_state: Res<State<GameState>>) -> bool {
+    *app_state.get() != AppState::Game || *game_state.get() == GameState::Paused
+}
+
 // systems ---------------------------------------------------------------------
 // -----------------------------------------------------------------------------
+// real time, so the focus animation keeps running while the game is paused
 pub fn animate_focused_material(
-    time: Res<Time>,
+    time: Res<Time<Real>>,
     mut ui_materials: ResMut<Assets<FocusedButtonMaterial>>,
 ) {
     for (_, material) in ui_materials.iter_mut() {
@@ -120,26 +155,26 @@ pub fn animate_focused_material(
 
 pub fn style_ui_element(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    time: Res<Time>,
-    mut normal_button_mat: ResMut<Assets<NormalButtonMaterial>>,
-    mut focused_button_mat: ResMut<Assets<FocusedButtonMaterial>>,
+    fonts: Res<FontAssets>,
+    ui_assets: Res<UiAssets>,
+    time: Res<Time<Real>>,
+    mut button_mat: ButtonMaterials,
     mut focus_query: Query<(Entity, &UiFocusable, &Children), Changed<UiFocusable>>,
     mut text_query: Query<&mut Text>,
 ) {
-    let texture_handle: Handle<Image> = asset_server.load(style_ui::SHADER_BUTTON_TEXTURE);
+    let texture_handle = &ui_assets.button_texture;
 
     for (ui_entity, focusable, children) in &mut focus_query {
         let mut text = text_query.get_mut(children[0]).unwrap();
 
         if focusable.is_focused {
             text.sections[0].style.color = style_ui::FOCUSED_TEXT_COLOR;
-            text.sections[0].style.font = asset_server.load(style_fonts::FONT_BODY_BOLD);
+            text.sections[0].style.font = fonts.body_bold.clone();
             text.sections[0].style.font_size = style_ui::FOCUSED_TEXT_SIZE;
             commands
                 .entity(ui_entity)
                 .remove::<Handle<NormalButtonMaterial>>()
-                .insert(focused_button_mat.add(FocusedButtonMaterial {
+                .insert(button_mat.focused.add(FocusedButtonMaterial {
                     time: time.elapsed_seconds(),
                     color_texture: Some(texture_handle.clone()),
                     alpha_mode: AlphaMode::Blend,
@@ -147,12 +182,12 @@ pub fn style_ui_element(
             debug!("UiFocusable change: set focus on entity {:?}", ui_entity);
         } else {
             text.sections[0].style.color = style_ui::NORMAL_TEXT_COLOR;
-            text.sections[0].style.font = asset_server.load(style_fonts::FONT_BODY);
+            text.sections[0].style.font = fonts.body.clone();
             text.sections[0].style.font_size = style_ui::NORMAL_TEXT_SIZE;
             commands
                 .entity(ui_entity)
                 .remove::<Handle<FocusedButtonMaterial>>()
-                .insert(normal_button_mat.add(NormalButtonMaterial {
+                .insert(button_mat.normal.add(NormalButtonMaterial {
                     color_texture: Some(texture_handle.clone()),
                     alpha_mode: AlphaMode::Blend,
                 }));
@@ -166,7 +201,12 @@ pub fn style_ui_element(
 
 pub fn handle_ui_navigation(
     action_state: Res<ActionState<controls::InputAction>>,
-    mut ui_element_query: Query<(&UiNavigation, &mut UiFocusable)>,
+    mut ui_element_query: Query<(
+        &UiNavigation,
+        &mut UiFocusable,
+        Option<&AutoNavigation>,
+        &GlobalTransform,
+    )>,
 ) {
     if action_state.just_pressed(&controls::InputAction::Move) {
         let input_direction = action_state
@@ -174,38 +214,245 @@ pub fn handle_ui_navigation(
             .unwrap()
             .xy();
 
-        // find the next ui_element to focus and un-set the current focused ui_element
-        let mut next_ui_element = UiElement::None;
-        for (ui_navigation, mut ui_focusable) in &mut ui_element_query {
-            if ui_focusable.is_focused {
-                if input_direction.y > 0.85 {
-                    next_ui_element = ui_navigation.up;
-                } else if input_direction.y < -0.85 {
-                    next_ui_element = ui_navigation.down;
-                } else if input_direction.x < -0.85 {
-                    next_ui_element = ui_navigation.left;
-                } else if input_direction.x > 0.85 {
-                    next_ui_element = ui_navigation.right;
-                }
+        let Some((ui_navigation, auto_navigation, origin)) = ui_element_query
+            .iter()
+            .find(|(_, ui_focusable, _, _)| ui_focusable.is_focused)
+            .map(|(ui_navigation, _, auto_navigation, transform)| {
+                (
+                    *ui_navigation,
+                    auto_navigation.copied(),
+                    transform.translation().truncate(),
+                )
+            })
+        else {
+            return;
+        };
+
+        // ui space has y pointing down, so up on the stick is -y on screen
+        let (explicit_ui_element, screen_direction) = if input_direction.y > 0.85 {
+            (ui_navigation.up, Vec2::NEG_Y)
+        } else if input_direction.y < -0.85 {
+            (ui_navigation.down, Vec2::Y)
+        } else if input_direction.x < -0.85 {
+            (ui_navigation.left, Vec2::NEG_X)
+        } else if input_direction.x > 0.85 {
+            (ui_navigation.right, Vec2::X)
+        } else {
+            return;
+        };
+
+        // find the next ui_element to focus, explicit links win over the spatial search
+        let next_ui_element = match (explicit_ui_element, auto_navigation) {
+            (UiElement::None, Some(auto_navigation)) => find_spatial_neighbour(
+                origin,
+                screen_direction,
+                auto_navigation.wrap,
+                ui_element_query
+                    .iter()
+                    .filter(|(other, _, _, _)| other.self_id != ui_navigation.self_id)
+                    .map(|(other, _, _, transform)| {
+                        (other.self_id, transform.translation().truncate())
+                    }),
+            ),
+            _ => explicit_ui_element,
+        };
 
-                // if the next ui element to focus is valid, un-set the current ui element
-                if next_ui_element != UiElement::None {
+        // un-set the current focused ui_element and set focus on the next one
+        if next_ui_element != UiElement::None {
+            for (other, mut ui_focusable, _, _) in &mut ui_element_query {
+                if other.self_id == ui_navigation.self_id {
                     ui_focusable.is_focused = false;
+                } else if other.self_id == next_ui_element {
+                    ui_focusable.is_focused = true;
                 }
-
-                break;
             }
         }
+    }
+}
 
-        // set focus on the next ui_element
-        if next_ui_element != UiElement::None {
-            for (ui_navigation, mut ui_focusable) in &mut ui_element_query {
-                if ui_navigation.self_id == next_ui_element {
-                    ui_focusable.is_focused = true;
+// closest candidate in the direction, off-axis distance weighs double so rows and columns
+//  are preferred over diagonals, wrapping picks the farthest candidate on the opposite side
+pub fn find_spatial_neighbour(
+    origin: Vec2,
+    direction: Vec2,
+    wrap: bool,
+    candidates: impl Iterator<Item = (UiElement, Vec2)>,
+) -> UiElement {
+    let mut nearest: Option<(UiElement, f32)> = None;
+    let mut wrapped: Option<(UiElement, f32)> = None;
 
-                    break;
-                }
+    for (ui_element, position) in candidates {
+        let offset = position - origin;
+        let along = offset.dot(direction);
+        let across = offset.perp_dot(direction).abs();
+        let score = along + 2.0 * across;
+
+        if along > 1.0 {
+            if nearest.is_none_or(|(_, best)| score < best) {
+                nearest = Some((ui_element, score));
             }
+        } else if along < -1.0 && wrapped.is_none_or(|(_, best)| score < best) {
+            wrapped = Some((ui_element, score));
+        }
+    }
+
+    match (nearest, wrap) {
+        (Some((ui_element, _)), _) => ui_element,
+        (None, true) => wrapped.map_or(UiElement::None, |(ui_element, _)| ui_element),
+        (None, false) => UiElement::None,
+    }
+}
+
+// pointer interaction is added here so each screen's button spawner does not need it
+pub fn add_pointer_interaction(
+    mut commands: Commands,
+    focusable_query: Query<Entity, (With<UiFocusable>, Without<Interaction>)>,
+) {
+    for focusable_entity in focusable_query.iter() {
+        commands
+            .entity(focusable_entity)
+            .insert(Interaction::default());
+    }
+}
+
+pub fn handle_ui_pointer(
+    toggle_actions: Res<ToggleActions<controls::InputAction>>,
+    mut action_state: ResMut<ActionState<controls::InputAction>>,
+    mut read_cursor_moved: EventReader<CursorMoved>,
+    mut ui_element_query: Query<(Entity, Ref<Interaction>, &mut UiFocusable)>,
+) {
+    // focus only follows the pointer after it moves, so a resting cursor does not
+    //  steal focus back from keyboard / gamepad navigation
+    let has_pointer_moved = read_cursor_moved.read().count() > 0;
+
+    // actions are disabled while another system owns the input, ie: binding capture
+    if !toggle_actions.enabled {
+        return;
+    }
+
+    let clicked_entity = ui_element_query
+        .iter()
+        .find(|(_, interaction, _)| {
+            interaction.is_changed() && **interaction == Interaction::Pressed
+        })
+        .map(|(entity, _, _)| entity);
+    let hovered_entity = ui_element_query
+        .iter()
+        .find(|(_, interaction, _)| **interaction != Interaction::None)
+        .map(|(entity, _, _)| entity);
+
+    let Some(pointer_entity) = clicked_entity.or(hovered_entity.filter(|_| has_pointer_moved))
+    else {
+        return;
+    };
+
+    // only touch the focusables that change so style_ui_element restyles just those
+    for (ui_entity, _, mut ui_focusable) in &mut ui_element_query {
+        let is_pointed = ui_entity == pointer_entity;
+        if ui_focusable.is_focused != is_pointed {
+            ui_focusable.is_focused = is_pointed;
         }
     }
+
+    if clicked_entity.is_some() {
+        action_state.press(&controls::InputAction::Select);
+    }
+}
+
+// tests -----------------------------------------------------------------------
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(index: usize) -> UiElement {
+        UiElement::Menu(MenuScreen::Title, index)
+    }
+
+    // a column of buttons 100 apart, ui space has y pointing down
+    fn column() -> Vec<(UiElement, Vec2)> {
+        (0..4)
+            .map(|index| (element(index), Vec2::new(0.0, index as f32 * 100.0)))
+            .collect()
+    }
+
+    #[test]
+    fn picks_the_closest_candidate_in_the_direction() {
+        let origin = Vec2::new(0.0, 100.0);
+        let candidates = column().into_iter().filter(|(e, _)| *e != element(1));
+
+        assert_eq!(
+            find_spatial_neighbour(origin, Vec2::Y, false, candidates.clone()),
+            element(2)
+        );
+        assert_eq!(
+            find_spatial_neighbour(origin, Vec2::NEG_Y, false, candidates),
+            element(0)
+        );
+    }
+
+    #[test]
+    fn prefers_the_same_column_over_a_closer_diagonal() {
+        let candidates = [
+            (element(1), Vec2::new(80.0, 60.0)),
+            (element(2), Vec2::new(0.0, 120.0)),
+        ];
+
+        assert_eq!(
+            find_spatial_neighbour(Vec2::ZERO, Vec2::Y, false, candidates.into_iter()),
+            element(2)
+        );
+    }
+
+    #[test]
+    fn ignores_candidates_on_the_same_line() {
+        // a row neighbour is not a candidate when moving down
+        let candidates = [(element(1), Vec2::new(200.0, 0.5))];
+
+        assert_eq!(
+            find_spatial_neighbour(Vec2::ZERO, Vec2::Y, true, candidates.into_iter()),
+            UiElement::None
+        );
+    }
+
+    #[test]
+    fn stops_at_the_edge_without_wrap() {
+        let origin = Vec2::new(0.0, 300.0);
+        let candidates = column().into_iter().filter(|(e, _)| *e != element(3));
+
+        assert_eq!(
+            find_spatial_neighbour(origin, Vec2::Y, false, candidates),
+            UiElement::None
+        );
+    }
+
+    #[test]
+    fn wraps_to_the_farthest_candidate_on_the_other_side() {
+        let origin = Vec2::new(0.0, 300.0);
+        let candidates = column().into_iter().filter(|(e, _)| *e != element(3));
+
+        assert_eq!(
+            find_spatial_neighbour(origin, Vec2::Y, true, candidates),
+            element(0)
+        );
+    }
+
+    #[test]
+    fn does_not_wrap_while_a_candidate_is_ahead() {
+        let origin = Vec2::new(0.0, 100.0);
+        let candidates = column().into_iter().filter(|(e, _)| *e != element(1));
+
+        assert_eq!(
+            find_spatial_neighbour(origin, Vec2::NEG_Y, true, candidates),
+            element(0)
+        );
+    }
+
+    #[test]
+    fn no_candidates_is_none() {
+        assert_eq!(
+            find_spatial_neighbour(Vec2::ZERO, Vec2::X, true, std::iter::empty()),
+            UiElement::None
+        );
+    }
 }